@@ -19,6 +19,9 @@ from os import PathLike
 from pathlib import Path
 from typing import Optional, Iterator, Callable, Protocol
 
+if typing.TYPE_CHECKING:
+    import numpy
+
 
 class DirectionKind(object):
     AToB: DirectionKind
@@ -65,6 +68,11 @@ class PyVocabulary:
     def save(self, path: str | Path | PathLike) -> int: ...
     @staticmethod
     def load(path: str | Path | PathLike) -> 'PyVocabulary': ...
+    def save_binary(self, path: str | Path | PathLike) -> None:
+        """Writes the vocabulary in a compact binary format, an existing file is overwritten."""
+        ...
+    @staticmethod
+    def load_binary(path: str | Path | PathLike) -> 'PyVocabulary': ...
 
     def to_json(self) -> str:...
 
@@ -153,10 +161,34 @@ class PyDictionary:
 
     def get_translation_a_to_b(self, word: str) -> list[str] | None: ...
     def get_translation_b_to_a(self, word: str) -> list[str] | None: ...
+    def translate_batch(self, words: list[str]) -> list[list[str] | None]:
+        """Translates all words from a to b, returns None for each unknown word."""
+        ...
+    def most_polysemous(self, n: int, side: str) -> list[tuple[str, int]]:
+        """Returns the n words of the side 'a' or 'b' with the most translations."""
+        ...
+    def jaccard_similarity_a(self, word_a1: str, word_a2: str) -> float | None:
+        """Returns the jaccard similarity of the translations of two words in language a."""
+        ...
+    def find_synonym_candidates_a(self, word_a: str, threshold: float) -> list[tuple[str, float]] | None:
+        """Returns the words in language a with a jaccard similarity of at least threshold to word_a."""
+        ...
+    def connected_components(self) -> list[list[tuple[str, LanguageKind]]]:
+        """The connected components of the translation graph, words without translations form their own component."""
+        ...
+    def largest_component(self) -> int: ...
+    def prune_orphans(self) -> tuple[int, int]:
+        """Removes all words without translations, returns the number of removed words in A and B."""
+        ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
     def __iter__(self) -> Iterator[tuple[tuple[int, str, None | SolvedMetadata], tuple[int, str, None | SolvedMetadata], DirectionKind]]: ...
-    def save(self, path: str | Path | PathLike): ...
+    def save(self, path: str | Path | PathLike, checksum: bool | None = None):
+        """Saves the dictionary, if checksum is set a checksum is embedded and verified by load."""
+        ...
+    def save_jsonl(self, path: str | Path | PathLike) -> int:
+        """Writes every translation pair with its metadata as a json object per line."""
+        ...
     @staticmethod
     def load(path: str | Path | PathLike) -> 'PyDictionary': ...
 
@@ -186,6 +218,19 @@ class PyTopicModel:
             document_lengths: list[int]
     ) -> None: ...
 
+    @staticmethod
+    def from_numpy(matrix: 'numpy.ndarray', vocabulary: PyVocabulary, used_vocab_frequency: list[int]) -> 'PyTopicModel':
+        """
+        Creates a topic model from a float64 matrix with the shape (k, vocabulary size).
+        The matrix is always copied once into the topic model, a matrix that is not
+        C-contiguous is copied to a contiguous matrix before that.
+        """
+        ...
+
+    def to_numpy(self) -> 'numpy.ndarray':
+        """Returns the topics as float64 matrix with the shape (k, vocabulary size), filled without intermediate copies."""
+        ...
+
     @property
     def k(self) -> int:...
 
@@ -203,6 +248,54 @@ class PyTopicModel:
         """Shows the top n word, by default 10."""
         ...
 
+    def show_html(self, top_n: int) -> str:
+        """Returns the top n words of every topic as a html table with inlined css."""
+        ...
+    def export_top_words_csv(self, path: str | Path | PathLike, top_n: int) -> None:
+        """Writes the top n words of every topic as csv with the columns topic_id, word and probability."""
+        ...
+    @staticmethod
+    def import_top_words_csv(path: str | Path | PathLike) -> 'PyTopicModel':
+        """
+        Reconstructs a topic model from a csv written by export_top_words_csv.
+        All words missing in a topic get a uniform epsilon.
+        """
+        ...
+    @staticmethod
+    def ensemble(models: list['PyTopicModel'], weights: list[float]) -> 'PyTopicModel':
+        """
+        Combines the models by the weighted average of their word probabilities.
+        The vocabulary is the union of all vocabularies, missing words get the smallest probability of all models.
+        """
+        ...
+    def document_frequency_vector(self) -> list[int]:
+        """
+        Approximates the number of documents containing each word.
+        A word is contained in a document if its expected count in the document is greater than 0.5.
+        """
+        ...
+    def tfidf_score_per_topic(self) -> list[list[float]]:
+        """Weights the topic-word probabilities by ln(N / df + 1)."""
+        ...
+    def compare_topics(self, topic_i: int, topic_j: int) -> float | None:
+        """
+        Returns the Jensen-Shannon divergence between two topics in bits.
+        Returns None if one of the topics does not exist.
+        """
+        ...
+    def diversity_score(self) -> float:
+        """Returns the mean Jensen-Shannon divergence over all pairs of topics."""
+        ...
+    def word_relevance_scores(self, lambda_: float = 0.6, global_word_probs: list[float] | None = None) -> list[list[float]]:
+        """
+        Returns the LDAvis relevance of every word in every topic.
+        If global_word_probs is not set, they are calculated from the used vocabulary frequencies.
+        """
+        ...
+    def top_n_by_relevance(self, n: int, lambda_: float = 0.6, global_word_probs: list[float] | None = None) -> list[list[str]]:
+        """Returns the n most relevant words of every topic."""
+        ...
+
     def get_doc_probability(self, doc: list[str], alpha: float | list[float], gamma_threshold: float,
                             minimum_probability: None | float = None,
                             minimum_phi_value: None | float = None,
@@ -285,14 +378,73 @@ class PyVoting:
         Executes the voting with the provided variables.
         Returns the result and the used voters.
         """
-    ...
+        ...
+
+    def with_fallback(self, fallback: 'PyVoting') -> 'PyVoting':
+        """
+        Returns a voting that executes the fallback if the evaluation of this voting fails.
+        Other errors, e.g. raised by python, are not handled by the fallback.
+        """
+        ...
+
+    def __str__(self) -> str: ...
+
+    def spy(self, audit: bool | None = None) -> 'PyVotingSpy':
+        """
+        Wraps the voting in a spy, if audit is set the variables read by the last call are recorded.
+        The global context needs topic_id, candidate_id and score_candidate, the voters need voter_id and score.
+        """
+        ...
+
+class PyVotingSpy:
+    def __call__(self, global_context: PyContextWithMutableVariables, voters: list[PyContextWithMutableVariables]) -> tuple[PyExprValue, list[PyContextWithMutableVariables]]:
+        """
+        Executes the voting with the provided variables.
+        Returns the result and the used voters.
+        """
+        ...
+
+    def last_audit_log(self) -> list[str]:
+        """The names of the variables read by the last call, first from the global context and then from the voters."""
+        ...
+
+    def score_distribution(self) -> ScoreDistribution | None:
+        """The distribution of the scores produced so far."""
+        ...
 
 class PyVotingRegistry:
     def __init__(self) -> None: ...
     def get_registered(self, name: str) -> PyVoting | None: ...
+    def parse_once(self, voting: str) -> PyVoting:
+        """Parses the voting, repeated calls with the same voting reuse the parsed voting."""
+        ...
     def register_at(self, name: str, voting: str): ...
+    def register_voting(self, name: str, voting: PyVoting):
+        """Registers an already created voting, e.g. a voting with a fallback, for the name."""
+        ...
     def register(self, voting: str): ...
 
+class ScoreDistribution:
+    """Statistics about the scores produced by a voting."""
+    @property
+    def min(self) -> float: ...
+    @property
+    def max(self) -> float: ...
+    @property
+    def mean(self) -> float: ...
+    @property
+    def std_dev(self) -> float: ...
+    @property
+    def p25(self) -> float: ...
+    @property
+    def p50(self) -> float: ...
+    @property
+    def p75(self) -> float: ...
+    @property
+    def histogram(self) -> list[tuple[float, int]]:
+        """The lower bound of each bin and the number of scores in it."""
+        ...
+
 
 class PyVariableProvider:
     def __init__(self, model: PyTopicModel, dictionary: PyDictionary) -> None: ...
@@ -370,6 +522,20 @@ class PyTranslationConfig:
         ...
 
 
+class MemoryReport:
+    """A snapshot of the memory usage of the process, all values are 0 if it is unavailable."""
+    @staticmethod
+    def current() -> 'MemoryReport': ...
+    @property
+    def rss_bytes(self) -> int: ...
+    @property
+    def virtual_bytes(self) -> int: ...
+    @property
+    def elapsed(self) -> float:
+        """The elapsed time in seconds."""
+        ...
+
+
 class VotingFunction(Protocol):
     """Defines the format of the voting function"""
     def __call__(self, global_context: PyContextWithMutableVariables, voters: list[PyContextWithMutableVariables]) -> PyExprValue:
@@ -377,7 +543,7 @@ class VotingFunction(Protocol):
 
 def create_topic_model_specific_dictionary(dictionary: PyDictionary, vocabulary: PyVocabulary) -> PyDictionary:
     """
-    Creates the specific dictionary used by the translation.
+    Creates the specific dictionary used by the translation, the metadata of the retained words is kept.
     Can be used for debugging.
     """
     ...
@@ -397,6 +563,51 @@ def translate_topic_model(
     ...
 
 
+def translate_topic_model_with_untranslated(
+        topic_model: PyTopicModel,
+        dictionary: PyDictionary,
+        voting: BuildInVoting | PyVoting | str | VotingFunction,
+        config: PyTranslationConfig,
+        provider: PyVariableProvider | None = None,
+        registry: PyVotingRegistry | None = None
+) -> tuple[PyTopicModel, list[str]]:
+    """
+    Translates a topic model and returns the normalized translation together with the words
+    of language A that have no translation candidate in any topic, kept original words do not count.
+    Throws an exception is something goes wrong.
+    """
+    ...
+
+
+class PyTranslationDiagnostics:
+    """The diagnostics of a translation, only the requested parts are set."""
+    @property
+    def untranslated(self) -> list[str] | None:
+        """The words of language A without any translation candidate in all topics."""
+        ...
+    @property
+    def topic_specific_dictionary(self) -> PyDictionary | None:
+        """The topic model specific dictionary used for the translation, including the metadata of its words."""
+        ...
+
+
+def translate_topic_model_with_diagnostics(
+        topic_model: PyTopicModel,
+        dictionary: PyDictionary,
+        voting: BuildInVoting | PyVoting | str | VotingFunction,
+        config: PyTranslationConfig,
+        provider: PyVariableProvider | None = None,
+        voting_registry: PyVotingRegistry | None = None,
+        report_untranslated: bool = True,
+        keep_topic_specific_dictionary: bool = True
+) -> tuple[PyTopicModel, PyTranslationDiagnostics]:
+    """
+    Translates a topic model and returns the normalized translation together with the requested diagnostics.
+    Throws an exception is something goes wrong.
+    """
+    ...
+
+
 class PyTopicModelBuilder:
     def __init__(self, language_a: None | str | LanguageHint = None):
         ...
@@ -451,12 +662,20 @@ class variable_names:
     "The importance rank of the word."
     SCORE: str
     "The score of the word in the topic model."
+    WEIGHT: str
+    "The external weight of a voter, 1.0 if not set."
     VOTER_ID: str
     "The word id of a voter."
     CANDIDATE_ID: str
     "The word id of a candidate."
     TOPIC_ID: str
     "The topic id."
+    TOPIC_SIMILARITY_SCORE: str
+    "The cosine similarity between two topics."
+    EMBEDDING_SIMILARITY_A: str
+    "The cosine similarity between the embedding of a voter and the centroid of the topic."
+    EMBEDDING_SIMILARITY_B: str
+    "The cosine similarity between the embedding of a candidate and the centroid of the topic."
 
 
 class PyArticle:
@@ -697,6 +916,10 @@ class PyTokenizerBuilder:
         """Sets the segmentation to the standard unicode separator. Deactivates the language detection feature."""
         ...
 
+    def keep_compounds(self, keep_compounds: bool) -> 'PyTokenizerBuilder':
+        """If set, words joined by a hyphen or slash (e.g. "e-mail" or "and/or") are kept as a single token."""
+        ...
+
     def stemmer(self, stemmer: PyStemmingAlgorithm, smart: bool = False) -> 'PyTokenizerBuilder':
         """Sets a snowball stemmer for a specific language, if smart is set to true, it will try to use the apropiate language for every language and fall back to stemmer if none fits the bill."""
         ...
@@ -17,9 +17,10 @@ use std::marker::PhantomData;
 use std::sync::{Arc, RwLock, OnceLock};
 use evalexpr::{ContextWithMutableVariables, EvalexprError, Value};
 use thiserror::Error;
-use crate::topicmodel::dictionary::{DictionaryMut, DictionaryWithVocabulary, FromVoc};
-use crate::topicmodel::topic_model::{TopicModelWithDocumentStats, TopicModelWithVocabulary};
-use crate::topicmodel::vocabulary::{MappableVocabulary, VocabularyMut};
+use crate::topicmodel::dictionary::{BasicDictionaryWithVocabulary, DictionaryMut, DictionaryWithVocabulary, FromVoc};
+use crate::topicmodel::topic_model::{BasicTopicModel, BasicTopicModelWithVocabulary, TopicModelWithDocumentStats, TopicModelWithVocabulary};
+use crate::topicmodel::vocabulary::{BasicVocabulary, MappableVocabulary, SearchableVocabulary, Vocabulary, VocabularyMut};
+use crate::variable_names::{EMBEDDING_SIMILARITY_A, EMBEDDING_SIMILARITY_B, TOPIC_SIMILARITY_SCORE};
 
 #[derive(Debug, Clone, Error)]
 pub enum VariableProviderError {
@@ -49,6 +50,13 @@ pub trait VariableProviderOut: Sync + Send {
     fn provide_for_word_b(&self, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
     fn provide_for_word_in_topic_a(&self, topic_id: usize, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
     fn provide_for_word_in_topic_b(&self, topic_id: usize, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
+
+    /// Provides variables describing the interaction between `topic_a` and `topic_b`.
+    /// Does nothing by default.
+    #[allow(unused_variables)]
+    fn provide_for_topic_pair(&self, topic_a: usize, topic_b: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -85,6 +93,7 @@ impl VariableProvider {
             pub fn add_for_word_b(&self, word_id: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()>;
             pub fn add_for_word_in_topic_a(&self, topic_id: usize, word_id: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()>;
             pub fn add_for_word_in_topic_b(&self, topic_id: usize, word_id: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()>;
+            pub fn add_for_topic_pair(&self, topic_a: usize, topic_b: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()>;
         }
     }
 }
@@ -98,6 +107,7 @@ impl VariableProviderOut for VariableProvider {
             fn provide_for_word_b(&self, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
             fn provide_for_word_in_topic_a(&self, topic_id: usize, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
             fn provide_for_word_in_topic_b(&self, topic_id: usize, word_id: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
+            fn provide_for_topic_pair(&self, topic_a: usize, topic_b: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()>;
         }
     }
 }
@@ -114,7 +124,8 @@ pub struct InnerVariableProvider {
     per_word_a: OnceLock<IdBasedVariableProvider<Words>>,
     per_word_b: OnceLock<IdBasedVariableProvider<Words>>,
     per_topic_per_word_a: OnceLock<TopicWiseWordVariableProvider>,
-    per_topic_per_word_b: OnceLock<TopicWiseWordVariableProvider>
+    per_topic_per_word_b: OnceLock<TopicWiseWordVariableProvider>,
+    per_topic_pair: OnceLock<TopicPairVariableProvider>
 }
 
 unsafe impl Send for InnerVariableProvider{}
@@ -131,7 +142,8 @@ impl InnerVariableProvider {
             per_word_a: OnceLock::new(),
             per_word_b: OnceLock::new(),
             per_topic_per_word_a: OnceLock::new(),
-            per_topic_per_word_b: OnceLock::new()
+            per_topic_per_word_b: OnceLock::new(),
+            per_topic_pair: OnceLock::new()
         }
     }
 
@@ -158,6 +170,10 @@ impl InnerVariableProvider {
     pub fn add_for_word_in_topic_b(&self, topic_id: usize, word_id: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()> {
         self.per_topic_per_word_b.get_or_init(|| TopicWiseWordVariableProvider::new(self.topic_count, self.word_count_b)).register_variable(topic_id, word_id, key, value)
     }
+
+    pub fn add_for_topic_pair(&self, topic_a: usize, topic_b: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()> {
+        self.per_topic_pair.get_or_init(|| TopicPairVariableProvider::new(self.topic_count)).register_variable(topic_a, topic_b, key, value)
+    }
 }
 
 impl VariableProviderOut for InnerVariableProvider {
@@ -206,6 +222,14 @@ impl VariableProviderOut for InnerVariableProvider {
             Ok(())
         }
     }
+
+    fn provide_for_topic_pair(&self, topic_a: usize, topic_b: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        if let Some(found) = self.per_topic_pair.get() {
+            found.provide_variables(topic_a, topic_b, target)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -372,4 +396,350 @@ impl TopicWiseWordVariableProvider {
     }
 }
 
+/// The variables of pairs of topics.
+#[derive(Debug, Clone)]
+pub struct TopicPairVariableProvider {
+    variables: Arc<RwLock<Vec<Vec<Vec<(String, Value)>>>>>
+}
+
+impl TopicPairVariableProvider {
+    pub fn new(topic_count: usize) -> Self {
+        Self {
+            variables: Arc::new(RwLock::new(vec![vec![Vec::new(); topic_count]; topic_count]))
+        }
+    }
+
+    pub fn register_variable(&self, topic_a: usize, topic_b: usize, key: impl AsRef<str>, value: impl Into<Value>) -> VariableProviderResult<()> {
+        let mut variable_lock = self.variables.write().unwrap();
+        let topic_count = variable_lock.len();
+        let data = variable_lock
+            .get_mut(topic_a)
+            .ok_or(VariableProviderError::TopicNotFound { topic_id: topic_a, topic_count })?
+            .get_mut(topic_b)
+            .ok_or(VariableProviderError::TopicNotFound { topic_id: topic_b, topic_count })?;
+        data.push((key.as_ref().to_string(), value.into()));
+        Ok(())
+    }
+
+    pub fn provide_variables(&self, topic_a: usize, topic_b: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        let variable_lock = self.variables.read().unwrap();
+        let topic_count = variable_lock.len();
+        let data = variable_lock
+            .get(topic_a)
+            .ok_or(VariableProviderError::TopicNotFound { topic_id: topic_a, topic_count })?
+            .get(topic_b)
+            .ok_or(VariableProviderError::TopicNotFound { topic_id: topic_b, topic_count })?;
+        for (k, v) in data {
+            target.set_value(k.clone(), v.clone())?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Provides the pre-computed cosine similarity between two topics as [TOPIC_SIMILARITY_SCORE].
+///
+/// The translation of the topic t provides the pair (t, t), a matrix comparing the topics of the
+/// translated model with the topics of a reference model allows votings to boost similar topics.
+#[derive(Debug, Clone)]
+pub struct TopicSimilarityProvider {
+    similarities: Arc<Vec<Vec<f64>>>
+}
+
+impl TopicSimilarityProvider {
+    /// Wraps a square matrix where `similarities[a][b]` is the similarity between topic a and b.
+    pub fn new(similarities: Vec<Vec<f64>>) -> Self {
+        Self { similarities: Arc::new(similarities) }
+    }
+
+    /// Computes the cosine similarity between all topics of `topic_model`.
+    pub fn from_topic_model(topic_model: &impl BasicTopicModel) -> Self {
+        let topics = topic_model.topics();
+        let norms = topics.iter().map(|topic| {
+            topic.iter().map(|value| value * value).sum::<f64>().sqrt()
+        }).collect::<Vec<_>>();
+        let similarities = topics.iter().zip(norms.iter()).map(|(topic_a, norm_a)| {
+            topics.iter().zip(norms.iter()).map(|(topic_b, norm_b)| {
+                let denominator = norm_a * norm_b;
+                if denominator == 0.0 {
+                    0.0
+                } else {
+                    topic_a.iter().zip(topic_b.iter()).map(|(a, b)| a * b).sum::<f64>() / denominator
+                }
+            }).collect::<Vec<_>>()
+        }).collect::<Vec<_>>();
+        Self::new(similarities)
+    }
+
+    pub fn similarity(&self, topic_a: usize, topic_b: usize) -> Option<f64> {
+        self.similarities.get(topic_a)?.get(topic_b).copied()
+    }
+
+    pub fn topic_count(&self) -> usize {
+        self.similarities.len()
+    }
+}
+
+impl<T> AsVariableProvider<T> for TopicSimilarityProvider {
+    fn as_variable_provider_for<'a, Model, D, Voc>(&self, topic_model: &'a Model, dictionary: &'a D) -> Result<VariableProvider, AsVariableProviderError> where
+        T: Hash + Eq + Ord + Clone,
+        Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
+        D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc>,
+        Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats
+    {
+        let variable_provider = VariableProvider::new(
+            topic_model.k(),
+            dictionary.voc_a().len(),
+            dictionary.voc_b().len()
+        );
+        for (topic_a, by_topic) in self.similarities.iter().enumerate() {
+            for (topic_b, similarity) in by_topic.iter().enumerate() {
+                variable_provider
+                    .add_for_topic_pair(topic_a, topic_b, TOPIC_SIMILARITY_SCORE, *similarity)
+                    .map_err(|err| AsVariableProviderError(err.to_string()))?;
+            }
+        }
+        Ok(variable_provider)
+    }
+}
+
+impl VariableProviderOut for TopicSimilarityProvider {
+    fn provide_global(&self, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_topic(&self, _: usize, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_word_a(&self, _: usize, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_word_b(&self, _: usize, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_word_in_topic_a(&self, _: usize, _: usize, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_word_in_topic_b(&self, _: usize, _: usize, _: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        Ok(())
+    }
+
+    fn provide_for_topic_pair(&self, topic_a: usize, topic_b: usize, target: &mut impl ContextWithMutableVariables) -> VariableProviderResult<()> {
+        let topic_count = self.topic_count();
+        let by_topic = self.similarities.get(topic_a).ok_or(VariableProviderError::TopicNotFound {
+            topic_id: topic_a,
+            topic_count
+        })?;
+        let similarity = by_topic.get(topic_b).ok_or(VariableProviderError::TopicNotFound {
+            topic_id: topic_b,
+            topic_count: by_topic.len()
+        })?;
+        target.set_value(TOPIC_SIMILARITY_SCORE.to_string(), Value::Float(*similarity))?;
+        Ok(())
+    }
+}
+
+/// Provides the cosine similarity between the embedding of a word and the centroid of a topic.
+/// The voters get [EMBEDDING_SIMILARITY_A] and the candidates [EMBEDDING_SIMILARITY_B].
+///
+/// The centroid of a topic is the probability-weighted mean of the embeddings of the words
+/// in the topic. Both embeddings have to live in the same (aligned) vector space and need the
+/// same dimension. Words without an embedding get a similarity of 0.0.
+///
+/// The centroids and similarities of all topics are computed by [AsVariableProvider::as_variable_provider_for],
+/// because the returned [VariableProvider] has to contain all values.
+#[derive(Debug, Clone)]
+pub struct EmbeddingBoostProvider<T> {
+    vocabulary: Arc<Vocabulary<T>>,
+    embeddings: Arc<Vec<Vec<f32>>>,
+    target_vocabulary: Arc<Vocabulary<T>>,
+    target_vectors: Arc<Vec<Vec<f32>>>,
+}
+
+impl<T> EmbeddingBoostProvider<T> {
+    /// `embeddings[id]` is the embedding of the word with `id` in `vocabulary` (language A),
+    /// `target_vectors[id]` the embedding of the word with `id` in `target_vocabulary` (language B).
+    pub fn new(
+        vocabulary: Vocabulary<T>,
+        embeddings: Vec<Vec<f32>>,
+        target_vocabulary: Vocabulary<T>,
+        target_vectors: Vec<Vec<f32>>
+    ) -> Self {
+        Self {
+            vocabulary: Arc::new(vocabulary),
+            embeddings: Arc::new(embeddings),
+            target_vocabulary: Arc::new(target_vocabulary),
+            target_vectors: Arc::new(target_vectors)
+        }
+    }
+}
+
+fn cosine_similarity(embedding: &[f32], centroid: &[f64]) -> f64 {
+    let dot = embedding.iter().zip(centroid.iter()).map(|(a, b)| *a as f64 * b).sum::<f64>();
+    let norm_a = embedding.iter().map(|value| (*value as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b = centroid.iter().map(|value| value * value).sum::<f64>().sqrt();
+    let denominator = norm_a * norm_b;
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+impl<T> AsVariableProvider<T> for EmbeddingBoostProvider<T> where T: Hash + Eq {
+    fn as_variable_provider_for<'a, Model, D, Voc>(&self, topic_model: &'a Model, dictionary: &'a D) -> Result<VariableProvider, AsVariableProviderError> where
+        T: Hash + Eq + Ord + Clone,
+        Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
+        D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc>,
+        Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats
+    {
+        let embeddings_model = topic_model.vocabulary().iter().map(|word| {
+            self.vocabulary.get_id::<T>(word).and_then(|id| self.embeddings.get(id))
+        }).collect::<Vec<_>>();
+        let embeddings_a = dictionary.voc_a().iter().map(|word| {
+            self.vocabulary.get_id::<T>(word).and_then(|id| self.embeddings.get(id))
+        }).collect::<Vec<_>>();
+        let embeddings_b = dictionary.voc_b().iter().map(|word| {
+            self.target_vocabulary.get_id::<T>(word).and_then(|id| self.target_vectors.get(id))
+        }).collect::<Vec<_>>();
+
+        let mut used_embeddings = embeddings_model.iter().chain(embeddings_a.iter()).chain(embeddings_b.iter()).flatten();
+        let dimension = used_embeddings.clone().next().map_or(0, |value| value.len());
+        if let Some(mismatch) = used_embeddings.find(|value| value.len() != dimension) {
+            return Err(AsVariableProviderError(format!(
+                "All embeddings need the same dimension, expected {dimension} but got {}!",
+                mismatch.len()
+            )))
+        }
+
+        let variable_provider = VariableProvider::new(
+            topic_model.k(),
+            dictionary.voc_a().len(),
+            dictionary.voc_b().len()
+        );
+
+        for (topic_id, topic) in topic_model.topics().iter().enumerate() {
+            let mut centroid = vec![0.0f64; dimension];
+            let mut weight = 0.0;
+            for (embedding, probability) in embeddings_model.iter().zip(topic.iter()) {
+                if let Some(embedding) = embedding {
+                    for (target, value) in centroid.iter_mut().zip(embedding.iter()) {
+                        *target += *value as f64 * probability;
+                    }
+                    weight += probability;
+                }
+            }
+            if weight > 0.0 {
+                centroid.iter_mut().for_each(|value| *value /= weight);
+            }
 
+            for (word_id, embedding) in embeddings_a.iter().enumerate() {
+                let similarity = embedding.map_or(0.0, |embedding| cosine_similarity(embedding, &centroid));
+                variable_provider
+                    .add_for_word_in_topic_a(topic_id, word_id, EMBEDDING_SIMILARITY_A, similarity)
+                    .map_err(|err| AsVariableProviderError(err.to_string()))?;
+            }
+
+            for (word_id, embedding) in embeddings_b.iter().enumerate() {
+                let similarity = embedding.map_or(0.0, |embedding| cosine_similarity(embedding, &centroid));
+                variable_provider
+                    .add_for_word_in_topic_b(topic_id, word_id, EMBEDDING_SIMILARITY_B, similarity)
+                    .map_err(|err| AsVariableProviderError(err.to_string()))?;
+            }
+        }
+
+        Ok(variable_provider)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use evalexpr::{Context, HashMapContext, Value};
+    use crate::external_variable_provider::{AsVariableProvider, EmbeddingBoostProvider, TopicSimilarityProvider, VariableProviderOut};
+    use crate::topicmodel::dictionary::{BasicDictionaryWithVocabulary, Dictionary, DictionaryMut};
+    use crate::topicmodel::dictionary::direction::Invariant;
+    use crate::topicmodel::topic_model::TopicModel;
+    use crate::topicmodel::vocabulary::{SearchableVocabulary, Vocabulary};
+    use crate::variable_names::{EMBEDDING_SIMILARITY_A, EMBEDDING_SIMILARITY_B, TOPIC_SIMILARITY_SCORE};
+
+    #[test]
+    fn provides_topic_similarity(){
+        let model = TopicModel::new(
+            vec![
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![2.0, 0.0],
+            ],
+            Vocabulary::<String>::from(vec!["a".to_string(), "b".to_string()]),
+            vec![1, 1],
+            vec![vec![1.0, 0.0, 0.0]],
+            vec![1]
+        );
+        let provider = TopicSimilarityProvider::from_topic_model(&model);
+        let mut context = HashMapContext::new();
+        provider.provide_for_topic_pair(0, 2, &mut context).unwrap();
+        assert_eq!(Some(&Value::Float(1.0)), context.get_value(TOPIC_SIMILARITY_SCORE));
+        provider.provide_for_topic_pair(0, 1, &mut context).unwrap();
+        assert_eq!(Some(&Value::Float(0.0)), context.get_value(TOPIC_SIMILARITY_SCORE));
+        assert!(provider.provide_for_topic_pair(0, 3, &mut context).is_err());
+    }
+
+    #[test]
+    fn provides_embedding_similarity(){
+        let model = TopicModel::new(
+            vec![
+                vec![1.0, 0.0],
+                vec![0.5, 0.5],
+            ],
+            Vocabulary::<String>::from(vec!["a".to_string(), "b".to_string()]),
+            vec![1, 1],
+            vec![vec![1.0, 0.0]],
+            vec![1]
+        );
+        let mut dictionary = Dictionary::<String, Vocabulary<String>>::new();
+        dictionary.insert::<Invariant>("a", "x");
+        dictionary.insert::<Invariant>("b", "y");
+
+        let provider = EmbeddingBoostProvider::new(
+            Vocabulary::<String>::from(vec!["b".to_string(), "a".to_string()]),
+            vec![vec![0.0, 1.0], vec![1.0, 0.0]],
+            Vocabulary::<String>::from(vec!["x".to_string(), "y".to_string()]),
+            vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+        ).as_variable_provider_for(&model, &dictionary).unwrap();
+
+        let get = |topic_id: usize, word_id_a: Option<usize>, word_id_b: Option<usize>| {
+            let mut context = HashMapContext::new();
+            if let Some(word_id) = word_id_a {
+                provider.provide_for_word_in_topic_a(topic_id, word_id, &mut context).unwrap();
+                context.get_value(EMBEDDING_SIMILARITY_A).unwrap().as_float().unwrap()
+            } else {
+                provider.provide_for_word_in_topic_b(topic_id, word_id_b.unwrap(), &mut context).unwrap();
+                context.get_value(EMBEDDING_SIMILARITY_B).unwrap().as_float().unwrap()
+            }
+        };
+
+        let a = dictionary.voc_a().get_id("a");
+        let b = dictionary.voc_a().get_id("b");
+        let x = dictionary.voc_b().get_id("x");
+        let y = dictionary.voc_b().get_id("y");
+
+        assert_eq!(1.0, get(0, a, None));
+        assert_eq!(0.0, get(0, b, None));
+        assert_eq!(1.0, get(0, None, x));
+        assert_eq!(0.0, get(0, None, y));
+        assert!((get(1, a, None) - std::f64::consts::FRAC_1_SQRT_2).abs() < 1E-10);
+        assert!((get(1, None, y) - std::f64::consts::FRAC_1_SQRT_2).abs() < 1E-10);
+
+        let mismatched = EmbeddingBoostProvider::new(
+            Vocabulary::<String>::from(vec!["b".to_string(), "a".to_string()]),
+            vec![vec![0.0, 1.0], vec![1.0, 0.0]],
+            Vocabulary::<String>::from(vec!["x".to_string(), "y".to_string()]),
+            vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]],
+        ).as_variable_provider_for(&model, &dictionary);
+        assert!(mismatched.is_err());
+    }
+}
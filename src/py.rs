@@ -21,6 +21,7 @@ use crate::py::translate::translate_module;
 use crate::py::variable_provider::variable_provider_module;
 use crate::py::vocabulary::vocabulary_module;
 use crate::py::voting::voting_module;
+use crate::toolkit::memory::register_py_memory;
 
 pub mod topic_model;
 pub mod vocabulary;
@@ -42,5 +43,6 @@ pub(crate) fn register_modules(m: &Bound<'_, PyModule>) -> PyResult<()>{
     voting_module(m)?;
     variable_provider_module(m)?;
     tokenizer_module(m)?;
+    register_py_memory(m)?;
     Ok(())
 }
@@ -18,7 +18,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::hash::Hash;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufReader, BufWriter};
 use std::ops::{Deref};
 use std::path::{PathBuf};
 use itertools::Itertools;
@@ -29,13 +29,14 @@ use pyo3::types::{PyFunction};
 use serde::{Deserialize, Serialize};
 use crate::py::helpers::{LanguageHintValue};
 use crate::py::vocabulary::PyVocabulary;
-use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable, DictionaryMut, DictionaryWithMeta, DictionaryWithVocabulary, FromVoc};
-use crate::topicmodel::dictionary::direction::{A, AToB, B, BToA, Direction, register_py_directions, DirectionKind, DirectionTuple, Invariant, Language, Translation};
+use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable, DictionaryMut, DictionaryWithMeta, DictionaryWithVocabulary, FromVoc, read_json_with_checksum, write_json_with_checksum};
+use crate::topicmodel::dictionary::direction::{A, AToB, B, BToA, Direction, register_py_directions, DirectionKind, DirectionTuple, Invariant, Language, LanguageKind, Translation};
 use crate::topicmodel::dictionary::iterators::{DictionaryWithMetaIterator, DictIter};
 use crate::topicmodel::dictionary::metadata::{register_py_metadata, SolvedMetadata};
 use crate::topicmodel::language_hint::LanguageHint;
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::vocabulary::{SearchableVocabulary, Vocabulary};
+use crate::topicmodel::ToTopicModelSpecific;
 
 #[derive(FromPyObject, Clone, Debug, Serialize, Deserialize)]
 pub enum SingleOrVec<T> {
@@ -488,6 +489,72 @@ impl PyDictionary {
             )
     }
 
+    fn translate_batch(&self, words: Vec<String>) -> Vec<Option<Vec<String>>> {
+        self.inner
+            .translate_words_a_to_b(&words.iter().map(|word| word.as_str()).collect_vec())
+            .into_iter()
+            .map(|translations| translations.map(|values| values.into_iter().cloned().collect_vec()))
+            .collect_vec()
+    }
+
+    fn most_polysemous(&self, n: usize, side: &str) -> PyResult<Vec<(String, usize)>> {
+        let side: LanguageKind = side.to_uppercase().parse().map_err(|_| PyValueError::new_err(format!("Unknown side {side}, expected a or b!")))?;
+        Ok(
+            match side {
+                LanguageKind::A => {
+                    self.inner.most_polysemous::<AToB>(n).into_iter().map(|(word_id, count)| {
+                        (self.inner.id_to_word::<A>(word_id).unwrap().to_string(), count)
+                    }).collect_vec()
+                }
+                LanguageKind::B => {
+                    self.inner.most_polysemous::<BToA>(n).into_iter().map(|(word_id, count)| {
+                        (self.inner.id_to_word::<B>(word_id).unwrap().to_string(), count)
+                    }).collect_vec()
+                }
+            }
+        )
+    }
+
+    fn jaccard_similarity_a(&self, word_a1: &str, word_a2: &str) -> Option<f64> {
+        let word_id1 = self.inner.word_to_id::<AToB, _>(word_a1)?;
+        let word_id2 = self.inner.word_to_id::<AToB, _>(word_a2)?;
+        Some(self.inner.jaccard_similarity_a(word_id1, word_id2))
+    }
+
+    fn find_synonym_candidates_a(&self, word_a: &str, threshold: f64) -> Option<Vec<(String, f64)>> {
+        let word_id = self.inner.word_to_id::<AToB, _>(word_a)?;
+        Some(
+            self.inner.find_synonym_candidates_a(word_id, threshold).into_iter().map(|(word_id, similarity)| {
+                (self.inner.id_to_word::<A>(word_id).unwrap().to_string(), similarity)
+            }).collect_vec()
+        )
+    }
+
+    fn connected_components(&self) -> Vec<Vec<(String, LanguageKind)>> {
+        self.inner.connected_components().into_iter().map(|component| {
+            component.into_iter().map(|(word_id, language)| {
+                let word = match language {
+                    LanguageKind::A => self.inner.id_to_word::<A>(word_id),
+                    LanguageKind::B => self.inner.id_to_word::<B>(word_id)
+                };
+                (word.unwrap().to_string(), language)
+            }).collect_vec()
+        }).collect_vec()
+    }
+
+    fn largest_component(&self) -> usize {
+        self.inner.largest_component()
+    }
+
+    /// Removes all words without translations, returns the number of removed words in A and B.
+    fn prune_orphans(&mut self) -> (usize, usize) {
+        let (remap_a, remap_b) = self.inner.prune_orphans();
+        (
+            remap_a.iter().filter(|value| value.is_none()).count(),
+            remap_b.iter().filter(|value| value.is_none()).count()
+        )
+    }
+
     fn __repr__(&self) -> String {
         format!("PyDictionary({:?})", self.inner)
     }
@@ -496,25 +563,31 @@ impl PyDictionary {
         self.inner.to_string()
     }
 
-    pub fn save(&self, path: PathBuf) -> PyResult<()> {
+    /// Saves the dictionary as json, if `checksum` is set a checksum of the content is embedded.
+    #[pyo3(signature = (path, checksum=None))]
+    pub fn save(&self, path: PathBuf, checksum: Option<bool>) -> PyResult<()> {
         let writer = File::options().write(true).create_new(true).open(path)?;
-        let mut writer = BufWriter::with_capacity(1024*32, writer);
-        match serde_json::to_writer(&mut writer, &self) {
-            Ok(_) => {
-                writer.flush()?;
-                Ok(())
-            }
+        let writer = BufWriter::with_capacity(1024*32, writer);
+        match write_json_with_checksum(self, writer, checksum.unwrap_or(false)) {
+            Ok(_) => Ok(()),
             Err(err) => {
                 return Err(PyValueError::new_err(err.to_string()))
             }
         }
     }
 
+    /// Writes every translation pair as a json object per line, returns the number of lines.
+    pub fn save_jsonl(&self, path: PathBuf) -> PyResult<usize> {
+        let writer = File::options().write(true).create_new(true).open(path)?;
+        let writer = BufWriter::with_capacity(1024*32, writer);
+        Ok(self.inner.write_jsonl(writer)?)
+    }
+
     #[staticmethod]
     pub fn load(path: PathBuf) -> PyResult<Self> {
         let reader = File::options().read(true).open(path)?;
-        let mut reader = BufReader::with_capacity(1024*32, reader);
-        match serde_json::from_reader(&mut reader) {
+        let reader = BufReader::with_capacity(1024*32, reader);
+        match read_json_with_checksum(reader) {
             Ok(result) => {Ok(result)}
             Err(err) => {
                 return Err(PyValueError::new_err(err.to_string()))
@@ -771,6 +844,14 @@ impl FromVoc<String, PyVocabulary> for PyDictionary {
     }
 }
 
+impl ToTopicModelSpecific<String, PyVocabulary> for PyDictionary {
+    fn to_topic_model_specific(&self, vocabulary: &PyVocabulary) -> Self {
+        Self {
+            inner: self.inner.to_topic_model_specific(vocabulary)
+        }
+    }
+}
+
 pub(crate) fn dictionary_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     register_py_directions(m)?;
     register_py_metadata(m)?;
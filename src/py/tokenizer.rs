@@ -1330,6 +1330,8 @@ impl PyAlignedArticleProcessor {
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PyTokenizerBuilder {
     unicode: bool,
+    #[serde(default)]
+    keep_compounds: bool,
     words_dict: Option<SpecialVec>,
     normalizer_option: PyNormalizerOption,
     segmenter_option: PySegmenterOption,
@@ -1384,6 +1386,11 @@ impl PyTokenizerBuilder {
         slf
     }
 
+    fn keep_compounds<'py>(slf: Bound<'py, Self>, keep_compounds: bool) -> Bound<'py, Self> {
+        slf.borrow_mut().keep_compounds = keep_compounds;
+        slf
+    }
+
     fn allow_list<'py>(slf: Bound<'py, Self>, allow_list: HashMap<PyScript, Vec<PyLanguage>>) -> Bound<'py, Self> {
         slf.borrow_mut().segmenter_option.set_allow_list(Some(allow_list));
         slf
@@ -1428,6 +1435,7 @@ impl PyTokenizerBuilder {
         builder.create_char_map(self.normalizer_option.create_char_map);
         builder.lossy_normalization(self.normalizer_option.lossy);
         builder.unicode(self.normalizer_option.lossy);
+        builder.keep_compounds(self.keep_compounds);
 
         if let Some(ref allow_list) = self.segmenter_option.allow_list {
             builder.allow_list(allow_list);
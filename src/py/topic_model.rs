@@ -22,6 +22,7 @@ use std::ops::Range;
 use std::path::{PathBuf};
 use std::sync::Arc;
 use itertools::Itertools;
+use pyo3::buffer::PyBuffer;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -32,7 +33,7 @@ use crate::py::topic_model_builder::PyTopicModelBuilder;
 use crate::py::vocabulary::PyVocabulary;
 use crate::toolkit::partial_ord_iterator::PartialOrderIterator;
 use crate::{topicmodel};
-use crate::topicmodel::enums::{ReadError, TopicModelVersion, WriteError};
+use crate::topicmodel::enums::{CsvLoadError, EnsembleError, ReadError, TopicModelVersion, WriteError};
 use crate::topicmodel::language_hint::LanguageHint;
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::topic_model::{BasicTopicModel, BasicTopicModelWithVocabulary, DocumentId, SingleOrList, TopicId, TopicModel, TopicModelInferencer, TopicModelWithDocumentStats, TopicModelWithVocabulary, WordId};
@@ -79,6 +80,51 @@ impl PyTopicModel {
         }
     }
 
+    /// Creates a topic model from a 2d float64 buffer (e.g. a numpy array) with the shape (k, vocabulary size).
+    /// The matrix is always copied once into the topic model, a buffer that is not C-contiguous
+    /// is copied to a contiguous buffer before that.
+    #[staticmethod]
+    fn from_numpy(matrix: &Bound<'_, PyAny>, vocabulary: PyVocabulary, used_vocab_frequency: Vec<u64>) -> PyResult<Self> {
+        let py = matrix.py();
+        let buffer = PyBuffer::<f64>::get_bound(matrix)?;
+        let (k, n) = match buffer.shape() {
+            &[k, n] => (k, n),
+            shape => return Err(PyValueError::new_err(format!("Expected a 2d matrix but got the shape {shape:?}!")))
+        };
+        if n != vocabulary.len() || n != used_vocab_frequency.len() {
+            return Err(PyValueError::new_err(format!(
+                "The matrix has {n} columns but the vocabulary has {} words and there are {} frequencies!",
+                vocabulary.len(),
+                used_vocab_frequency.len()
+            )))
+        }
+        let topics = if n == 0 {
+            vec![Vec::new(); k]
+        } else if let Some(data) = buffer.as_slice(py) {
+            data.chunks(n).map(|row| row.iter().map(|value| value.get()).collect_vec()).collect_vec()
+        } else {
+            buffer.to_vec(py)?.chunks(n).map(<[f64]>::to_vec).collect_vec()
+        };
+        Ok(Self::new(topics, vocabulary, used_vocab_frequency, Vec::new(), Vec::new()))
+    }
+
+    /// Returns the topics as numpy float64 array with the shape (k, vocabulary size).
+    /// The topics are written directly into the buffer of the array instead of nested python lists.
+    fn to_numpy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let k = self.inner.topic_count();
+        let n = self.inner.topics().first().map_or(0, |topic| topic.len());
+        let array = py.import_bound("numpy")?.call_method1("empty", ((k, n), "float64"))?;
+        let buffer = PyBuffer::<f64>::get_bound(&array)?;
+        let Some(cells) = buffer.as_mut_slice(py) else {
+            return Err(PyRuntimeError::new_err("The numpy array is not a writeable C-contiguous buffer!"))
+        };
+        for (cell, value) in cells.iter().zip(self.inner.topics().iter().flatten()) {
+            cell.set(*value);
+        }
+        buffer.release(py);
+        Ok(array)
+    }
+
     #[getter]
     #[pyo3(name="k")]
     fn py_k(&self) -> usize {
@@ -109,6 +155,67 @@ impl PyTopicModel {
         Ok(())
     }
 
+    fn show_html(&self, top_n: usize) -> PyResult<String> {
+        let mut out = Vec::new();
+        self.inner.to_html(&mut out, top_n)?;
+        String::from_utf8(out).map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Writes the top n words of every topic as csv with the columns topic_id, word and probability.
+    fn export_top_words_csv(&self, path: PathBuf, top_n: usize) -> PyResult<()> {
+        Ok(self.inner.export_top_words_per_topic_csv(BufWriter::new(File::create(path)?), top_n)?)
+    }
+
+    /// Reconstructs a topic model from a csv written by export_top_words_csv.
+    #[staticmethod]
+    fn import_top_words_csv(path: PathBuf) -> PyResult<Self> {
+        Ok(Self { inner: TopicModel::import_top_words_from_csv(BufReader::new(File::open(path)?))? })
+    }
+
+    /// Combines the models by the weighted average of their word probabilities.
+    #[staticmethod]
+    fn ensemble(models: Vec<PyRef<PyTopicModel>>, weights: Vec<f64>) -> PyResult<Self> {
+        let models = models.iter().map(|model| &model.inner).collect_vec();
+        Ok(Self { inner: TopicModel::ensemble(&models, &weights)? })
+    }
+
+    fn document_frequency_vector(&self) -> Vec<u32> {
+        self.inner.document_frequency_vector()
+    }
+
+    fn tfidf_score_per_topic(&self) -> Vec<Vec<f64>> {
+        self.inner.tfidf_score_per_topic()
+    }
+
+    fn compare_topics(&self, topic_i: usize, topic_j: usize) -> Option<f64> {
+        self.inner.compare_topics(topic_i, topic_j)
+    }
+
+    fn diversity_score(&self) -> f64 {
+        self.inner.diversity_score()
+    }
+
+    #[pyo3(signature = (lambda_=0.6, global_word_probs=None))]
+    fn word_relevance_scores(&self, lambda_: f64, global_word_probs: Option<Vec<f64>>) -> PyResult<Vec<Vec<f64>>> {
+        let global_word_probs = global_word_probs.unwrap_or_else(|| self.inner.global_word_probabilities());
+        self.inner.word_relevance_scores(lambda_, &global_word_probs).ok_or_else(|| {
+            PyValueError::new_err(format!("Expected {} global word probabilities but got {}!", self.inner.vocabulary_size(), global_word_probs.len()))
+        })
+    }
+
+    #[pyo3(signature = (n, lambda_=0.6, global_word_probs=None))]
+    fn top_n_by_relevance(&self, n: usize, lambda_: f64, global_word_probs: Option<Vec<f64>>) -> PyResult<Vec<Vec<String>>> {
+        let global_word_probs = global_word_probs.unwrap_or_else(|| self.inner.global_word_probabilities());
+        let top_n = self.inner.top_n_by_relevance(lambda_, &global_word_probs, n).ok_or_else(|| {
+            PyValueError::new_err(format!("Expected {} global word probabilities but got {}!", self.inner.vocabulary_size(), global_word_probs.len()))
+        })?;
+        Ok(
+            top_n.into_iter().map(|word_ids| {
+                word_ids.into_iter().map(|word_id| self.inner.vocabulary().get_value(word_id).unwrap().to_string()).collect()
+            }).collect()
+        )
+    }
+
     fn __repr__(&self) -> String {
         format!("PyTopicModel({:?})", self.inner)
     }
@@ -405,6 +512,18 @@ impl From<ReadError<Infallible>> for PyErr {
     }
 }
 
+impl From<CsvLoadError> for PyErr {
+    fn from(err: CsvLoadError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+impl From<EnsembleError> for PyErr {
+    fn from(err: EnsembleError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
 
 pub(crate) fn topic_model_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTopicModel>()?;
@@ -25,9 +25,10 @@ use crate::py::variable_provider::PyVariableProvider;
 use crate::py::vocabulary::PyVocabulary;
 use crate::py::voting::{PyVoting, PyVotingRegistry};
 use crate::translate::{KeepOriginalWord, register_py_translate, TranslateConfig};
-use crate::voting::parser::input::ParserInput;
-use crate::voting::parser::{parse};
 use crate::translate::translate_topic_model as translate;
+use crate::translate::translate_topic_model_with_untranslated as translate_with_untranslated;
+use crate::translate::translate_topic_model_with_diagnostics as translate_with_diagnostics;
+use crate::translate::TranslationDiagnostics;
 use crate::topicmodel::topic_model::MappableTopicModel;
 use crate::variable_names::{register_py_variable_names_module};
 use crate::voting::{VotingMethod, VotingMethodContext, VotingResult};
@@ -83,6 +84,17 @@ impl VotingMethod for Wrapper<'_> {
             }
         }
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        match self {
+            Wrapper::External(value) => {
+                value.required_voter_fields()
+            }
+            Wrapper::Internal(value) => {
+                value.required_voter_fields()
+            }
+        }
+    }
 }
 
 impl VotingMethodMarker for Wrapper<'_> {}
@@ -94,9 +106,10 @@ impl PyTranslationConfig {
                 Wrapper::Internal(voting)
             }
             VotingArg::Parseable(voting) => {
-                match parse::<nom::error::Error<_>>(ParserInput::new(&voting, voting_registry.unwrap_or_default().registry())) {
-                    Ok((_, value)) => {
-                        Wrapper::Internal(value.into())
+                // A registry passed by the caller caches the parsed voting for the following translations.
+                match voting_registry.unwrap_or_default().registry().parse_once(&voting) {
+                    Ok(value) => {
+                        Wrapper::Internal(PyVoting::from_shared(value))
                     }
                     Err(err) => {
                         return Err(PyValueError::new_err(err.to_string()))
@@ -143,10 +156,95 @@ pub fn translate_topic_model<'a>(
     }
 }
 
+/// Translates the topic model and returns the words of language A without any candidate in all topics.
+#[pyfunction]
+pub fn translate_topic_model_with_untranslated<'a>(
+    topic_model: &PyTopicModel,
+    dictionary: &PyDictionary,
+    voting: VotingArg<'a>,
+    config: PyTranslationConfig,
+    provider: Option<&PyVariableProvider>,
+    voting_registry: Option<PyVotingRegistry>
+) -> PyResult<(PyTopicModel, Vec<String>)> {
+    let cfg = config.to_translation_config(voting, voting_registry)?.with_untranslated_report(true);
+    match translate_with_untranslated(topic_model, dictionary, &cfg, provider) {
+        Ok((result, untranslated)) => {
+            Ok((
+                PyTopicModel::wrap(result.map::<PyVocabulary>()),
+                untranslated.unwrap_or_default().iter().map(|word| word.to_string()).collect()
+            ))
+        }
+        Err(err) => {
+            Err(PyValueError::new_err(err.to_string()))
+        }
+    }
+}
+
+/// The diagnostics of a translation, only the requested parts are set.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct PyTranslationDiagnostics {
+    inner: TranslationDiagnostics<String, PyDictionary>
+}
+
+#[pymethods]
+impl PyTranslationDiagnostics {
+    #[getter]
+    fn untranslated(&self) -> Option<Vec<String>> {
+        Some(self.inner.untranslated.as_ref()?.iter().map(|word| word.to_string()).collect())
+    }
+
+    #[getter]
+    fn topic_specific_dictionary(&self) -> Option<PyDictionary> {
+        self.inner.topic_specific_dictionary.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyTranslationDiagnostics({:?})", self.inner)
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+/// Translates the topic model and returns the requested diagnostics.
+#[pyfunction]
+#[pyo3(signature = (topic_model, dictionary, voting, config, provider=None, voting_registry=None, report_untranslated=true, keep_topic_specific_dictionary=true))]
+pub fn translate_topic_model_with_diagnostics<'a>(
+    topic_model: &PyTopicModel,
+    dictionary: &PyDictionary,
+    voting: VotingArg<'a>,
+    config: PyTranslationConfig,
+    provider: Option<&PyVariableProvider>,
+    voting_registry: Option<PyVotingRegistry>,
+    report_untranslated: bool,
+    keep_topic_specific_dictionary: bool,
+) -> PyResult<(PyTopicModel, PyTranslationDiagnostics)> {
+    let cfg = config
+        .to_translation_config(voting, voting_registry)?
+        .with_untranslated_report(report_untranslated)
+        .with_topic_specific_dictionary(keep_topic_specific_dictionary);
+    match translate_with_diagnostics(topic_model, dictionary, &cfg, provider) {
+        Ok((result, diagnostics)) => {
+            Ok((
+                PyTopicModel::wrap(result.map::<PyVocabulary>()),
+                PyTranslationDiagnostics { inner: diagnostics }
+            ))
+        }
+        Err(err) => {
+            Err(PyValueError::new_err(err.to_string()))
+        }
+    }
+}
+
 
 pub(crate) fn translate_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTranslationConfig>()?;
+    m.add_class::<PyTranslationDiagnostics>()?;
     m.add_function(wrap_pyfunction!(translate_topic_model, m)?)?;
+    m.add_function(wrap_pyfunction!(translate_topic_model_with_untranslated, m)?)?;
+    m.add_function(wrap_pyfunction!(translate_topic_model_with_diagnostics, m)?)?;
     register_py_translate(m)?;
     register_py_variable_names_module(m)?;
     Ok(())
@@ -14,6 +14,7 @@
 
 use std::borrow::{Borrow};
 use std::convert::Infallible;
+use std::fs::File;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::io::Write;
@@ -27,7 +28,7 @@ use pyo3::prelude::{PyModule, PyModuleMethods};
 use serde::{Deserialize, Serialize};
 use crate::py::dictionary::{PyDictionary};
 use crate::py::helpers::{LanguageHintValue, ListOrInt};
-use crate::topicmodel::create_topic_model_specific_dictionary as create_topic_model_specific_dictionary_impl;
+use crate::topicmodel::ToTopicModelSpecific;
 use crate::topicmodel::language_hint::{LanguageHint, register_py_language_hint};
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::vocabulary::{LoadableVocabulary, MappableVocabulary, StoreableVocabulary, BasicVocabulary, Vocabulary, VocabularyMut, SearchableVocabulary};
@@ -122,6 +123,23 @@ impl PyVocabulary {
         Ok(self.inner.save_to_file(path)?)
     }
 
+    /// Writes the vocabulary in a compact binary format, an existing file is overwritten like by `save`.
+    fn save_binary(&self, path: PathBuf) -> PyResult<()> {
+        Ok(self.inner.write_binary(File::options().create(true).truncate(true).write(true).open(path)?)?)
+    }
+
+    #[staticmethod]
+    fn load_binary(path: PathBuf) -> PyResult<PyVocabulary> {
+        match Vocabulary::from_binary_reader(File::open(path)?) {
+            Ok(inner) => {
+                Ok(Self{ inner })
+            }
+            Err(value) => {
+                Err(PyValueError::new_err(value.to_string()))
+            }
+        }
+    }
+
     #[staticmethod]
     fn load(path: PathBuf) -> PyResult<PyVocabulary> {
         match Vocabulary::<String>::load_from_file(path) {
@@ -333,7 +351,7 @@ impl PyVocIter {
 
 #[pyfunction]
 pub fn create_topic_model_specific_dictionary(dictionary: &PyDictionary, vocabulary: &PyVocabulary) -> PyDictionary {
-    create_topic_model_specific_dictionary_impl(dictionary, vocabulary)
+    dictionary.to_topic_model_specific(vocabulary)
 }
 
 pub(crate) fn vocabulary_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -22,9 +22,10 @@ use pyo3::prelude::*;
 use crate::voting::parser::input::ParserInput;
 use crate::voting::parser::{parse, InterpretedVoting};
 use crate::voting::registry::VotingRegistry;
-use crate::voting::{register_py_voting_buildin, VotingMethod, VotingMethodContext, VotingResult};
+use crate::voting::{register_py_voting_buildin, FallbackVoting, VotingMethod, VotingMethodContext, VotingResult};
 use crate::voting::py::{PyContextWithMutableVariables, PyExprValue, register_py_voting_filters};
 use crate::voting::traits::VotingMethodMarker;
+use crate::voting::spy::{ScoreDistribution, Spy};
 
 #[pyclass]
 #[derive(Clone, Debug, Default)]
@@ -52,6 +53,14 @@ impl PyVotingRegistry {
         self.inner.get(name).map(PyVoting::from)
     }
 
+    /// Parses the voting, repeated calls with the same voting reuse the parsed voting.
+    pub fn parse_once(&self, voting: &str) -> PyResult<PyVoting> {
+        match self.inner.parse_once(voting) {
+            Ok(parsed) => Ok(PyVoting::from_shared(parsed)),
+            Err(err) => Err(PyValueError::new_err(err.to_string()))
+        }
+    }
+
     pub fn register_at(&self, name: &str, voting: &str) -> PyResult<()> {
         let parsed = parse::<Error<_>>(ParserInput::new(voting, &self.inner)).finish();
         match parsed {
@@ -60,7 +69,7 @@ impl PyVotingRegistry {
                     InterpretedVoting::BuildIn(_) => {
                         return Err(PyValueError::new_err("BuildIn functions can not be registered!".to_string()))
                     }
-                    InterpretedVoting::FromRegistry(_) => {
+                    InterpretedVoting::FromRegistry(_) | InterpretedVoting::Registered(_, _) => {
                         return Err(PyValueError::new_err("The name is already registered!".to_string()))
                     }
                     InterpretedVoting::Parsed(parsed) => {
@@ -76,6 +85,10 @@ impl PyVotingRegistry {
                     InterpretedVoting::Limited(_) => {
                         return Err(PyValueError::new_err("You can not register a limited method!".to_string()))
                     }
+                    fallback @ InterpretedVoting::Fallback(_) => {
+                        self.inner.register_method(name.to_string(), fallback);
+                        Ok(())
+                    }
                 }
             }
             Err(err) => {
@@ -84,6 +97,31 @@ impl PyVotingRegistry {
         }
     }
 
+    /// Registers an already created voting, e.g. a voting with a fallback, for `name`.
+    pub fn register_voting(&self, name: &str, voting: PyVoting) -> PyResult<()> {
+        match Arc::try_unwrap(voting.0).unwrap_or_else(|shared| shared.as_ref().clone()) {
+            InterpretedVoting::BuildIn(_) => {
+                Err(PyValueError::new_err("BuildIn functions can not be registered!".to_string()))
+            }
+            InterpretedVoting::Parsed(parsed) => {
+                self.inner.register(name.to_string(), parsed);
+                Ok(())
+            }
+            InterpretedVoting::FromRegistry(func) => {
+                self.inner.register_arc(name.to_string(), func);
+                Ok(())
+            }
+            InterpretedVoting::ForRegistry(value) => {
+                self.inner.register(name.to_string(), value.1);
+                Ok(())
+            }
+            other => {
+                self.inner.register_method(name.to_string(), other);
+                Ok(())
+            }
+        }
+    }
+
     pub fn register(&self, voting: &str) -> PyResult<()> {
         let parsed = parse::<Error<_>>(ParserInput::new(voting, &self.inner)).finish();
         match parsed {
@@ -92,7 +130,7 @@ impl PyVotingRegistry {
                     InterpretedVoting::BuildIn(_) => {
                         return Err(PyValueError::new_err("BuildIn functions can not be registered!".to_string()))
                     }
-                    InterpretedVoting::FromRegistry(_) => {
+                    InterpretedVoting::FromRegistry(_) | InterpretedVoting::Registered(_, _) => {
                         return Err(PyValueError::new_err("The name is already registered!".to_string()))
                     }
                     InterpretedVoting::Parsed(_) => {
@@ -106,6 +144,9 @@ impl PyVotingRegistry {
                     InterpretedVoting::Limited(_) => {
                         return Err(PyValueError::new_err("You can not register a limited method!".to_string()))
                     }
+                    InterpretedVoting::Fallback(_) => {
+                        return Err(PyValueError::new_err("You can not register a method with a fallback!".to_string()))
+                    }
                 }
             }
             Err(err) => {
@@ -118,7 +159,7 @@ impl PyVotingRegistry {
 
 #[pyclass]
 #[derive(Clone, Debug)]
-pub struct PyVoting(InterpretedVoting);
+pub struct PyVoting(Arc<InterpretedVoting>);
 
 #[pymethods]
 impl PyVoting {
@@ -126,7 +167,7 @@ impl PyVoting {
     pub fn parse(value: String, registry: Option<PyVotingRegistry>) -> PyResult<Self> {
         match parse::<Error<_>>(ParserInput::new(&value, registry.unwrap_or_default().registry())).finish() {
             Ok((_, parse_result)) => {
-                Ok(Self(parse_result))
+                Ok(Self(Arc::new(parse_result)))
             }
             Err(err) => {
                 Err(PyValueError::new_err(err.to_string()))
@@ -146,19 +187,83 @@ impl PyVoting {
             }
         }
     }
+
+    pub fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Returns a voting that executes `fallback` if the evaluation of this voting fails.
+    pub fn with_fallback(&self, fallback: PyVoting) -> PyVoting {
+        PyVoting::from(
+            InterpretedVoting::Fallback(
+                FallbackVoting::new(
+                    Box::new(self.0.as_ref().clone()),
+                    Box::new(fallback.0.as_ref().clone())
+                )
+            )
+        )
+    }
+
+    /// Wraps the voting in a spy, if audit is set the variables read by the last call are recorded.
+    pub fn spy(&self, audit: Option<bool>) -> PyVotingSpy {
+        PyVotingSpy {
+            inner: Spy::new(self.clone()).with_audit_log(audit.unwrap_or_default())
+        }
+    }
 }
 
 impl VotingMethod for PyVoting {
     fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
         self.0.execute(global_context, voters)
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        self.0.required_voter_fields()
+    }
 }
 
 impl VotingMethodMarker for PyVoting{}
 
+impl PyVoting {
+    /// Wraps an already shared voting, e.g. from [VotingRegistry::parse_once], without copying it.
+    pub(crate) fn from_shared(voting: Arc<InterpretedVoting>) -> Self {
+        Self(voting)
+    }
+}
+
 impl<T> From<T> for PyVoting where T: Into<InterpretedVoting> {
     fn from(value: T) -> Self {
-        Self(value.into())
+        Self(Arc::new(value.into()))
+    }
+}
+
+/// A voting that records its executions.
+#[pyclass]
+pub struct PyVotingSpy {
+    inner: Spy<PyVoting>
+}
+
+#[pymethods]
+impl PyVotingSpy {
+    //noinspection DuplicatedCode
+    pub fn __call__(&self, mut global_context: PyContextWithMutableVariables, mut voters: Vec<PyContextWithMutableVariables>) -> PyResult<(PyExprValue, Vec<PyContextWithMutableVariables>)>{
+        match self.inner.execute_with_voters(&mut global_context, voters.as_mut_slice()) {
+            Ok((value, used_voters)) => {
+                Ok((value.into(), used_voters.to_vec()))
+            }
+            Err(err) => {
+                Err(PyValueError::new_err(err.to_string()))
+            }
+        }
+    }
+
+    /// The names of the variables read by the last call.
+    pub fn last_audit_log(&self) -> Vec<String> {
+        self.inner.last_audit_log()
+    }
+
+    pub fn score_distribution(&self) -> Option<ScoreDistribution> {
+        self.inner.collect_score_distribution()
     }
 }
 
@@ -167,6 +272,8 @@ impl<T> From<T> for PyVoting where T: Into<InterpretedVoting> {
 pub(crate) fn voting_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyVoting>()?;
     m.add_class::<PyVotingRegistry>()?;
+    m.add_class::<PyVotingSpy>()?;
+    m.add_class::<ScoreDistribution>()?;
     register_py_voting_buildin(m)?;
     register_py_voting_filters(m)?;
     Ok(())
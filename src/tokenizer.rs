@@ -16,6 +16,7 @@ mod phrase_recognizer;
 mod stemming;
 mod unicode_segmenter;
 mod reconstruct_or_unicode;
+mod compound;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -25,6 +26,7 @@ use charabia::segmenter::{SegmentedStrIter, SegmentedTokenIter};
 use fst::Set;
 use rust_stemmers::{Algorithm};
 use trie_rs::map::Trie;
+use crate::tokenizer::compound::CompoundTokenIter;
 use crate::tokenizer::phrase_recognizer::{PhraseRecognizerIter};
 use crate::tokenizer::reconstruct_or_unicode::SegmentedIter;
 use crate::tokenizer::stemming::{SmartStemmer, StemmedTokenIter};
@@ -33,6 +35,7 @@ use crate::tokenizer::unicode_segmenter::UnicodeSegmenterTokenIter;
 /// A builder for a tokenizer
 pub struct TokenizerBuilder<'tb, A> {
     unicode: bool,
+    keep_compounds: bool,
     tokenizer_builder: CTokenizerBuilder<'tb, A>,
     normalizer_option: NormalizerOption<'tb>,
     stemmer: Option<(Algorithm, bool)>,
@@ -49,6 +52,7 @@ impl<'tb, A> TokenizerBuilder<'tb, A> {
     pub fn new() -> Self {
         Self {
             unicode: false,
+            keep_compounds: false,
             tokenizer_builder: CTokenizerBuilder::new(),
             normalizer_option: NormalizerOption {
                 create_char_map: false,
@@ -75,6 +79,15 @@ impl<'tb, A> TokenizerBuilder<'tb, A> {
         self.unicode = unicode;
         self
     }
+
+    /// If set, alphabetic words joined by a hyphen or slash (e.g. "e-mail" or "and/or") are kept as a single token.
+    ///
+    /// Only applies to [Tokenizer::reconstruct] and [Tokenizer::stem], [Tokenizer::tokenize],
+    /// [Tokenizer::segment] and [Tokenizer::segment_str] always split compounds.
+    pub fn keep_compounds(&mut self, keep_compounds: bool) -> &mut Self {
+        self.keep_compounds = keep_compounds;
+        self
+    }
 }
 
 
@@ -123,6 +136,7 @@ impl<'tb, A: AsRef<[u8]>> TokenizerBuilder<'tb, A>  {
     pub fn build(&'tb mut self) -> Tokenizer {
         Tokenizer::new(
             self.unicode,
+            self.keep_compounds,
             self.tokenizer_builder.build(),
             Cow::Borrowed(&self.normalizer_option),
             self.stemmer.map(SmartStemmer::from),
@@ -133,6 +147,7 @@ impl<'tb, A: AsRef<[u8]>> TokenizerBuilder<'tb, A>  {
     pub fn into_tokenizer(self) -> Tokenizer<'tb>  {
         Tokenizer::new(
             self.unicode,
+            self.keep_compounds,
             self.tokenizer_builder.into_tokenizer(),
             Cow::Owned(self.normalizer_option),
             self.stemmer.map(SmartStemmer::from),
@@ -145,6 +160,7 @@ impl<'tb, A: AsRef<[u8]>> TokenizerBuilder<'tb, A>  {
 
 pub struct Tokenizer<'tb> {
     unicode: bool,
+    keep_compounds: bool,
     tokenizer: CTokenizer<'tb>,
     normalizer_option: Cow<'tb, NormalizerOption<'tb>>,
     stemmer: Option<SmartStemmer>,
@@ -152,8 +168,8 @@ pub struct Tokenizer<'tb> {
 }
 
 impl<'tb> Tokenizer<'tb> {
-    pub fn new(unicode: bool, tokenizer: CTokenizer<'tb>, normalizer_option: Cow<'tb, NormalizerOption<'tb>>, stemmer: Option<SmartStemmer>, trie: Option<Cow<'tb, Trie<u8, usize>>>) -> Self {
-        Self { unicode, tokenizer, stemmer, trie, normalizer_option }
+    pub fn new(unicode: bool, keep_compounds: bool, tokenizer: CTokenizer<'tb>, normalizer_option: Cow<'tb, NormalizerOption<'tb>>, stemmer: Option<SmartStemmer>, trie: Option<Cow<'tb, Trie<u8, usize>>>) -> Self {
+        Self { unicode, keep_compounds, tokenizer, stemmer, trie, normalizer_option }
     }
 
     /// Allows to wrap a tokenizer for phrase recognition
@@ -173,27 +189,32 @@ impl<'tb> Tokenizer<'tb> {
     ///
     /// The provided text is segmented creating tokens,
     /// then tokens are normalized and classified depending on the list of normalizers and classifiers in [`normalizer::NORMALIZERS`].
+    /// Compounds are always split, see [TokenizerBuilder::keep_compounds].
     #[inline(always)]
     pub fn tokenize<'t, 'o>(&'t self, original: &'o str) -> NormalizedTokenIter<'o, 't> {
         self.tokenizer.tokenize(original)
     }
 
     /// Same as [`tokenize`] but attaches each [`Token`] to its corresponding portion of the original text.
-    pub fn reconstruct<'t, 'o>(&'t self, original: &'o str) -> SegmentedIter<'o, 't> {
-        if self.unicode {
+    /// Joins compounds if configured.
+    pub fn reconstruct<'t, 'o>(&'t self, original: &'o str) -> CompoundTokenIter<'o, 't> {
+        let segmented = if self.unicode {
             SegmentedIter::Unicode(UnicodeSegmenterTokenIter::new(original, &self.normalizer_option))
         } else {
             SegmentedIter::Reconstructor(self.tokenizer.reconstruct(original))
-        }
+        };
+        CompoundTokenIter::new(segmented, original, self.keep_compounds)
     }
 
     /// Segments the provided text creating an Iterator over [`Token`].
+    /// Compounds are always split, see [TokenizerBuilder::keep_compounds].
     #[inline(always)]
     pub fn segment<'t, 'o>(&'t self, original: &'o str) -> SegmentedTokenIter<'o, 't> {
         self.tokenizer.segment(original)
     }
 
     /// Segments the provided text creating an Iterator over `&str`.
+    /// Compounds are always split, see [TokenizerBuilder::keep_compounds].
     #[inline(always)]
     pub fn segment_str<'t, 'o>(&'t self, original: &'o str) -> SegmentedStrIter<'o, 't> {
         self.tokenizer.segment_str(original)
@@ -0,0 +1,123 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use charabia::Token;
+use crate::tokenizer::phrase_recognizer::merge_token_for_phrase;
+use crate::tokenizer::reconstruct_or_unicode::SegmentedIter;
+
+/// Returns true if `value` joins two words to a compound.
+#[inline]
+fn is_compound_separator(value: &str) -> bool {
+    value == "-" || value == "/"
+}
+
+#[inline]
+fn is_alphabetic(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(char::is_alphabetic)
+}
+
+/// Joins alphabetic words separated by a single hyphen or slash (e.g. "e-mail" or "and/or") to a single token.
+pub struct CompoundTokenIter<'o, 'tb> {
+    token_iter: SegmentedIter<'o, 'tb>,
+    original: &'o str,
+    keep_compounds: bool,
+    buffer: VecDeque<(&'o str, Token<'o>)>,
+}
+
+impl<'o, 'tb> CompoundTokenIter<'o, 'tb> {
+    pub fn new(token_iter: SegmentedIter<'o, 'tb>, original: &'o str, keep_compounds: bool) -> Self {
+        Self { token_iter, original, keep_compounds, buffer: VecDeque::with_capacity(2) }
+    }
+
+    fn next_token(&mut self) -> Option<(&'o str, Token<'o>)> {
+        self.buffer.pop_front().or_else(|| self.token_iter.next())
+    }
+
+    fn fill_buffer(&mut self, size: usize) {
+        while self.buffer.len() < size {
+            match self.token_iter.next() {
+                None => break,
+                Some(value) => self.buffer.push_back(value)
+            }
+        }
+    }
+
+    fn combine(&self, word: (&'o str, Token<'o>), separator: (&'o str, Token<'o>), other: (&'o str, Token<'o>)) -> (&'o str, Token<'o>) {
+        let lemma = format!("{}{}{}", word.1.lemma(), separator.0, other.1.lemma());
+        let token = merge_token_for_phrase(
+            Cow::Owned(lemma),
+            merge_token_for_phrase(Cow::Borrowed(""), word.1, separator.1),
+            other.1
+        );
+        (&self.original[token.byte_start..token.byte_end], token)
+    }
+}
+
+impl<'o, 'tb> Iterator for CompoundTokenIter<'o, 'tb> {
+    type Item = (&'o str, Token<'o>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.keep_compounds {
+            return self.token_iter.next()
+        }
+        let mut current = self.next_token()?;
+        if !is_alphabetic(current.0) {
+            return Some(current)
+        }
+        loop {
+            self.fill_buffer(2);
+            if self.buffer.len() < 2 || !is_compound_separator(self.buffer[0].0) || !is_alphabetic(self.buffer[1].0) {
+                return Some(current)
+            }
+            let separator = self.buffer.pop_front().unwrap();
+            let other = self.buffer.pop_front().unwrap();
+            current = self.combine(current, separator, other);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::tokenizer::TokenizerBuilder;
+
+    fn tokens(keep_compounds: bool, unicode: bool, text: &str) -> Vec<String> {
+        let mut builder = TokenizerBuilder::default();
+        builder.keep_compounds(keep_compounds);
+        builder.unicode(unicode);
+        let tokenizer = builder.build();
+        tokenizer
+            .reconstruct(text)
+            .filter(|(original, _)| !original.trim().is_empty())
+            .map(|(original, _)| original.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn can_keep_compounds(){
+        for unicode in [false, true] {
+            let split = tokens(false, unicode, "send an e-mail and/or call");
+            assert!(split.contains(&"e".to_string()));
+            assert!(split.contains(&"mail".to_string()));
+            assert!(!split.contains(&"e-mail".to_string()));
+            assert!(!split.contains(&"and/or".to_string()));
+
+            let kept = tokens(true, unicode, "send an e-mail and/or call");
+            assert_eq!(vec!["send", "an", "e-mail", "and/or", "call"], kept);
+        }
+        assert_eq!(vec!["well-to-do", "4", "2"], tokens(true, true, "well-to-do 4-2"));
+    }
+}
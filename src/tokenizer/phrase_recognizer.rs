@@ -145,7 +145,7 @@ impl<'a, 'o> SupportsPhrasing<'o> for Cow<'a, str> {
 }
 
 
-fn merge_token_for_phrase<'a>(phrase: Cow<'a, str>, a: Token<'_>, b: Token<'_>) -> Token<'a> {
+pub(super) fn merge_token_for_phrase<'a>(phrase: Cow<'a, str>, a: Token<'_>, b: Token<'_>) -> Token<'a> {
     let char_map = if let Some(mut char_map) = a.char_map {
         if let Some(other_char_map) = b.char_map {
             char_map.extend(other_char_map);
@@ -19,7 +19,7 @@ use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
 use charabia::{Language, Token};
 use rust_stemmers::{Algorithm, Stemmer};
-use crate::tokenizer::reconstruct_or_unicode::SegmentedIter;
+use crate::tokenizer::compound::CompoundTokenIter;
 use crate::tokenizer::stemming::SmartStemmer::Simple;
 
 trait Private{}
@@ -257,13 +257,13 @@ impl<'o> Stem for (&'o str, Token<'_>) {
 
 
 pub struct StemmedTokenIter<'o, 'tb> {
-    token_iter: SegmentedIter<'o, 'tb>,
+    token_iter: CompoundTokenIter<'o, 'tb>,
     stemmer: Option<&'tb SmartStemmer>,
     _phantom: PhantomData<&'o ()>
 }
 
 impl<'o, 'tb> StemmedTokenIter<'o, 'tb> {
-    pub fn new(token_iter: SegmentedIter<'o, 'tb>, stemmer: Option<&'tb SmartStemmer>) -> Self {
+    pub fn new(token_iter: CompoundTokenIter<'o, 'tb>, stemmer: Option<&'tb SmartStemmer>) -> Self {
         Self { token_iter, stemmer, _phantom: PhantomData }
     }
 }
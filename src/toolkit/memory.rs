@@ -0,0 +1,179 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use pyo3::{pyclass, pymethods, Bound, PyResult};
+use pyo3::prelude::{PyModule, PyModuleMethods};
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the memory usage of the current process.
+/// All values are 0 on platforms where the memory usage is unavailable.
+#[pyclass]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryReport {
+    /// The resident set size in bytes.
+    pub rss_bytes: u64,
+    /// The size of the virtual memory in bytes.
+    pub virtual_bytes: u64,
+    /// The time since the creation of the [MemoryReporter].
+    pub elapsed: Duration,
+}
+
+impl MemoryReport {
+    /// Creates a report of the current memory usage.
+    pub fn current(elapsed: Duration) -> Self {
+        let (rss_bytes, virtual_bytes) = read_memory_usage().unwrap_or_default();
+        Self {
+            rss_bytes,
+            virtual_bytes,
+            elapsed
+        }
+    }
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rss_bytes={} virtual_bytes={} elapsed_ms={}", self.rss_bytes, self.virtual_bytes, self.elapsed.as_millis())
+    }
+}
+
+#[pymethods]
+impl MemoryReport {
+    #[staticmethod]
+    #[pyo3(name = "current")]
+    fn py_current() -> Self {
+        Self::current(Duration::ZERO)
+    }
+
+    #[getter]
+    fn rss_bytes(&self) -> u64 {
+        self.rss_bytes
+    }
+
+    #[getter]
+    fn virtual_bytes(&self) -> u64 {
+        self.virtual_bytes
+    }
+
+    /// The elapsed time in seconds.
+    #[getter]
+    fn elapsed(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// Reports the memory usage of the current process and tracks the peak of the observed resident set size.
+#[derive(Debug)]
+pub struct MemoryReporter {
+    start: Instant,
+    peak_rss_bytes: AtomicU64,
+}
+
+impl MemoryReporter {
+    pub fn new() -> Self {
+        let reporter = Self {
+            start: Instant::now(),
+            peak_rss_bytes: AtomicU64::new(0),
+        };
+        reporter.create_structured_report();
+        reporter
+    }
+
+    /// Creates a report of the current memory usage and updates the peak.
+    pub fn create_structured_report(&self) -> MemoryReport {
+        let report = MemoryReport::current(self.start.elapsed());
+        self.peak_rss_bytes.fetch_max(report.rss_bytes, Ordering::Relaxed);
+        report
+    }
+
+    /// The maximum resident set size in bytes observed since the creation of the reporter.
+    pub fn peak_memory_bytes(&self) -> u64 {
+        self.peak_rss_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Updates the peak for the finished `phase` and logs a report on the debug level.
+    /// The memory usage is read independent of the log level.
+    pub fn log_phase(&self, phase: impl Display) {
+        let report = self.create_structured_report();
+        log::debug!("memory phase={phase} {report} peak_rss_bytes={}", self.peak_memory_bytes());
+    }
+}
+
+impl Default for MemoryReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the resident set size and the virtual memory size in bytes.
+#[cfg(target_os = "linux")]
+fn read_memory_usage() -> Option<(u64, u64)> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let read_kb = |key: &str| -> Option<u64> {
+        let line = status.lines().find(|line| line.starts_with(key))?;
+        let value = line[key.len()..].trim().strip_suffix("kB")?.trim();
+        value.parse::<u64>().ok().map(|value| value * 1024)
+    };
+    Some((read_kb("VmRSS:")?, read_kb("VmSize:")?))
+}
+
+/// The memory usage is unavailable on this platform.
+#[cfg(not(target_os = "linux"))]
+fn read_memory_usage() -> Option<(u64, u64)> {
+    None
+}
+
+pub(crate) fn register_py_memory(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<MemoryReport>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::toolkit::memory::MemoryReporter;
+
+    #[test]
+    fn peak_is_the_maximum_of_the_reports(){
+        let reporter = MemoryReporter::new();
+        let first = reporter.create_structured_report();
+        let data = vec![1u8; 16 * 1024 * 1024];
+        let second = reporter.create_structured_report();
+        assert!(reporter.peak_memory_bytes() >= first.rss_bytes.max(second.rss_bytes));
+        assert!(second.elapsed >= first.elapsed);
+        if cfg!(target_os = "linux") {
+            assert!(first.rss_bytes > 0);
+            assert!(first.virtual_bytes >= first.rss_bytes);
+        }
+        drop(data);
+    }
+
+    #[test]
+    fn phases_update_the_peak(){
+        let reporter = MemoryReporter::new();
+        reporter.log_phase("test");
+        if cfg!(target_os = "linux") {
+            assert!(reporter.peak_memory_bytes() > 0);
+        }
+    }
+}
@@ -15,10 +15,10 @@
 use std::hash::Hash;
 use rayon::prelude::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
-use crate::topicmodel::dictionary::{DictionaryMut, FromVoc};
+use crate::topicmodel::dictionary::{BasicDictionaryWithMeta, Dictionary, DictionaryMut, DictionaryWithMeta, FromVoc, FromVocWithMeta};
 use crate::topicmodel::dictionary::direction::*;
 use crate::topicmodel::reference::HashRef;
-use crate::topicmodel::vocabulary::{MappableVocabulary, VocabularyMut};
+use crate::topicmodel::vocabulary::{BasicVocabulary, MappableVocabulary, VocabularyMut};
 
 pub mod topic_model;
 pub mod vocabulary;
@@ -46,7 +46,98 @@ pub fn create_topic_model_specific_dictionary<D2, D1, T, V1, V2>(
         vocabulary.clone().map(|value| value.clone()),
         dictionary.language::<B>().cloned()
     );
+    insert_topic_model_specific_translations(&mut new_dict, dictionary);
+    return new_dict;
+}
+
+/// Same as [create_topic_model_specific_dictionary], but the metadata of all words
+/// retained in the new dictionary is copied from `dictionary`.
+pub fn create_topic_model_specific_dictionary_with_meta<D2, D1, T, V1, V2>(
+    dictionary: &D1,
+    vocabulary: &V1
+) -> D2
+    where
+        V1: VocabularyMut<T> + MappableVocabulary<T> + Clone,
+        V2: VocabularyMut<T>,
+        T: Eq + Hash + Clone,
+        D1: DictionaryMut<T, V1> + BasicDictionaryWithMeta,
+        D2: DictionaryMut<T, V2> + FromVocWithMeta<T, V2>
+{
+    let mut new_dict: D2 = D2::from_voc_lang_with_meta::<A>(
+        vocabulary.clone().map(|value| value.clone()),
+        dictionary.language::<B>().cloned(),
+        dictionary.metadata()
+    );
+    insert_topic_model_specific_translations(&mut new_dict, dictionary);
+
+    fn copy_metadata<L: Language, T: Eq + Hash, V1: VocabularyMut<T>, V2: VocabularyMut<T>>(
+        new_dict: &mut (impl DictionaryMut<T, V2> + BasicDictionaryWithMeta),
+        dictionary: &(impl DictionaryMut<T, V1> + BasicDictionaryWithMeta)
+    ) {
+        let voc = if L::LANG.is_a() {
+            new_dict.voc_a()
+        } else {
+            new_dict.voc_b()
+        };
+        let ids = voc.iter().enumerate().filter_map(|(new_id, word)| {
+            let old_id = if L::LANG.is_a() {
+                dictionary.word_to_id::<AToB, T>(word.as_ref())
+            } else {
+                dictionary.word_to_id::<BToA, T>(word.as_ref())
+            }?;
+            Some((new_id, old_id))
+        }).collect::<Vec<_>>();
+        for (new_id, old_id) in ids {
+            new_dict.metadata_mut().copy_meta_from::<L>(new_id, dictionary.metadata(), old_id);
+        }
+    }
+
+    copy_metadata::<A, T, V1, V2>(&mut new_dict, dictionary);
+    copy_metadata::<B, T, V1, V2>(&mut new_dict, dictionary);
+
+    return new_dict;
+}
+
+/// A dictionary that can create its own topic model specific dictionary,
+/// dictionaries with metadata keep the metadata of the retained words.
+pub trait ToTopicModelSpecific<T, V> {
+    /// Creates the topic model specific dictionary for `vocabulary`.
+    fn to_topic_model_specific(&self, vocabulary: &V) -> Self;
+}
 
+impl<T, V> ToTopicModelSpecific<T, V> for Dictionary<T, V>
+    where
+        T: Eq + Hash + Clone,
+        V: VocabularyMut<T> + MappableVocabulary<T> + Clone + Default
+{
+    fn to_topic_model_specific(&self, vocabulary: &V) -> Self {
+        create_topic_model_specific_dictionary(self, vocabulary)
+    }
+}
+
+impl<T, V> ToTopicModelSpecific<T, V> for DictionaryWithMeta<T, V>
+    where
+        T: Eq + Hash + Clone,
+        V: VocabularyMut<T> + MappableVocabulary<T> + Clone + Default
+{
+    fn to_topic_model_specific(&self, vocabulary: &V) -> Self {
+        create_topic_model_specific_dictionary_with_meta(self, vocabulary)
+    }
+}
+
+/// Inserts all translations of `dictionary` for the words in language A of `new_dict`
+/// and afterwards the retranslations for the resulting words in language B.
+fn insert_topic_model_specific_translations<D2, D1, T, V1, V2>(
+    new_dict: &mut D2,
+    dictionary: &D1
+)
+    where
+        V1: VocabularyMut<T>,
+        V2: VocabularyMut<T>,
+        T: Eq + Hash,
+        D1: DictionaryMut<T, V1>,
+        D2: DictionaryMut<T, V2>
+{
     let translations: Vec<(HashRef<T>, Option<Vec<&HashRef<T>>>)> = {
         new_dict.voc_a().as_ref().par_iter().map(|value| {
             (value.clone(), dictionary.translate_value_to_values::<AToB, _>(value))
@@ -69,21 +160,20 @@ pub fn create_topic_model_specific_dictionary<D2, D1, T, V1, V2>(
 
 
 
-    insert_into::<A, _, _>(&mut new_dict, &translations);
+    insert_into::<A, _, _>(new_dict, &translations);
 
     let retranslations = new_dict.voc_b().as_ref().par_iter().map(|value| {
         (value.clone(), dictionary.translate_value_to_values::<BToA, _>(value))
     }).collect::<Vec<_>>();
 
-    insert_into::<B, _, _>(&mut new_dict, &retranslations);
-
-    return new_dict;
+    insert_into::<B, _, _>(new_dict, &retranslations);
 }
 
 #[cfg(test)]
 mod test {
-    use crate::topicmodel::create_topic_model_specific_dictionary;
-    use crate::topicmodel::dictionary::{BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable};
+    use crate::topicmodel::{create_topic_model_specific_dictionary, create_topic_model_specific_dictionary_with_meta, ToTopicModelSpecific};
+    use crate::topicmodel::dictionary::{BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable, DictionaryWithMeta, DictionaryWithVocabulary};
+    use crate::topicmodel::dictionary::direction::{AToB, A};
     use crate::topicmodel::reference::HashRef;
     use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary, Vocabulary};
 
@@ -122,4 +212,33 @@ mod test {
 
         // println!("{}", dict);
     }
+
+    #[test]
+    fn can_transfer_metadata(){
+        let (voc_a, _, dict) = crate::translate::test::create_test_data();
+        let mut dict: DictionaryWithMeta<_, Vocabulary<_>> = dict.into();
+        let plane = dict.word_to_id::<AToB, _>("plane").unwrap();
+        let wing = dict.word_to_id::<AToB, _>("wing").unwrap();
+        dict.metadata_mut().set_subject_for::<A>(plane, "aviation");
+        dict.metadata_mut().set_subject_for::<A>(wing, "anatomy");
+
+        let voc = voc_a.filter_by_value(
+            |a| {
+                a.eq(&HashRef::new("plane".to_string())) || a.eq(&HashRef::new("aircraft".to_string()))
+            }
+        );
+
+        let d: DictionaryWithMeta<_, Vocabulary<_>> = create_topic_model_specific_dictionary_with_meta(
+            &dict,
+            &voc
+        );
+
+        let new_plane = d.word_to_id::<AToB, _>("plane").unwrap();
+        assert!(d.metadata().get_meta_ref::<A>(new_plane).unwrap().has_subject("aviation"));
+        assert!(d.word_to_id::<AToB, _>("wing").is_none());
+
+        let d = dict.to_topic_model_specific(&voc);
+        let new_plane = d.word_to_id::<AToB, _>("plane").unwrap();
+        assert!(d.metadata().get_meta_ref::<A>(new_plane).unwrap().has_subject("aviation"));
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -168,6 +169,107 @@ impl<T, V> Dictionary<T, V> where T: Eq + Hash, V: MappableVocabulary<T> {
     }
 }
 
+impl<T, V> Dictionary<T, V> where V: BasicVocabulary<T> {
+    /// Removes all translations where `predicate` returns false for the word in A, the word in B
+    /// and the direction of the translation. Both mappings are kept consistent.
+    pub fn apply_translation_filter<F>(&mut self, predicate: F) where F: Fn(&T, &T, DirectionKind) -> bool {
+        let voc_a = &self.voc_a;
+        let voc_b = &self.voc_b;
+        let removed = Self::collect_translations_to_remove(&self.map_a_to_b, &self.map_b_to_a, |a, b, direction| {
+            predicate(voc_a.get_value(a).unwrap().as_ref(), voc_b.get_value(b).unwrap().as_ref(), direction)
+        });
+        self.remove_translations(&removed);
+    }
+}
+
+impl<T, V> Dictionary<T, V> where T: Eq + Hash, V: VocabularyMut<T> + From<Option<LanguageHint>> {
+    /// Removes all words without any outgoing or incoming translation and compacts the ids,
+    /// the remaining words keep their relative order.
+    /// Returns the new ids of the old ids of language A and B, `None` if the word was removed.
+    pub fn prune_orphans(&mut self) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        fn find_connected(len: usize, outgoing: &[Vec<usize>], incoming: &[Vec<usize>]) -> Vec<bool> {
+            let mut connected = (0..len).map(|id| outgoing.get(id).is_some_and(|value| !value.is_empty())).collect_vec();
+            for id in incoming.iter().flatten() {
+                if let Some(value) = connected.get_mut(*id) {
+                    *value = true;
+                }
+            }
+            connected
+        }
+
+        fn compact<T: Eq + Hash, V: VocabularyMut<T> + From<Option<LanguageHint>>>(voc: &V, connected: &[bool]) -> (V, Vec<Option<usize>>) {
+            let mut new_voc = V::from(voc.language().cloned());
+            let remap = connected.iter().enumerate().map(|(id, connected)| {
+                connected.then(|| new_voc.add_hash_ref(voc.get_value(id).unwrap().clone()))
+            }).collect_vec();
+            (new_voc, remap)
+        }
+
+        fn remap_mapping(mapping: &[Vec<usize>], remap_from: &[Option<usize>], remap_to: &[Option<usize>]) -> Vec<Vec<usize>> {
+            mapping
+                .iter()
+                .enumerate()
+                .filter(|(id, _)| remap_from.get(*id).is_some_and(|value| value.is_some()))
+                .map(|(_, translations)| translations.iter().filter_map(|id| remap_to[*id]).collect_vec())
+                .collect_vec()
+        }
+
+        let connected_a = find_connected(self.voc_a.len(), &self.map_a_to_b, &self.map_b_to_a);
+        let connected_b = find_connected(self.voc_b.len(), &self.map_b_to_a, &self.map_a_to_b);
+        let (voc_a, remap_a) = compact(&self.voc_a, &connected_a);
+        let (voc_b, remap_b) = compact(&self.voc_b, &connected_b);
+        self.map_a_to_b = remap_mapping(&self.map_a_to_b, &remap_a, &remap_b);
+        self.map_b_to_a = remap_mapping(&self.map_b_to_a, &remap_b, &remap_a);
+        self.voc_a = voc_a;
+        self.voc_b = voc_b;
+        (remap_a, remap_b)
+    }
+}
+
+impl<T, V> Dictionary<T, V> {
+    /// Collects the `(a, b)` pairs of all translations where `keep` returns false,
+    /// a translation in both directions is only checked once as [DirectionKind::Invariant].
+    pub(in crate::topicmodel::dictionary) fn collect_translations_to_remove<F>(map_a_to_b: &[Vec<usize>], map_b_to_a: &[Vec<usize>], keep: F) -> HashSet<(usize, usize)> where F: Fn(usize, usize, DirectionKind) -> bool {
+        let mut removed = HashSet::new();
+        for (a, translations) in map_a_to_b.iter().enumerate() {
+            for b in translations.iter().copied() {
+                let direction = if map_b_to_a.get(b).is_some_and(|value| value.contains(&a)) {
+                    DirectionKind::Invariant
+                } else {
+                    DirectionKind::AToB
+                };
+                if !keep(a, b, direction) {
+                    removed.insert((a, b));
+                }
+            }
+        }
+        for (b, translations) in map_b_to_a.iter().enumerate() {
+            for a in translations.iter().copied() {
+                if map_a_to_b.get(a).is_some_and(|value| value.contains(&b)) {
+                    continue
+                }
+                if !keep(a, b, DirectionKind::BToA) {
+                    removed.insert((a, b));
+                }
+            }
+        }
+        removed
+    }
+
+    /// Removes all `(a, b)` pairs in `removed` from both mappings.
+    pub(in crate::topicmodel::dictionary) fn remove_translations(&mut self, removed: &HashSet<(usize, usize)>) {
+        if removed.is_empty() {
+            return
+        }
+        for (a, translations) in self.map_a_to_b.iter_mut().enumerate() {
+            translations.retain(|b| !removed.contains(&(a, *b)));
+        }
+        for (b, translations) in self.map_b_to_a.iter_mut().enumerate() {
+            translations.retain(|a| !removed.contains(&(*a, b)));
+        }
+    }
+}
+
 impl<T, V> DictionaryWithVocabulary<T, V> for Dictionary<T, V> where V: BasicVocabulary<T> {
     fn can_translate_id<D: Translation>(&self, id: usize) -> bool {
         if D::DIRECTION.is_a_to_b() {
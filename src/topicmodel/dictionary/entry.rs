@@ -0,0 +1,75 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::hash::Hash;
+use serde::{Deserialize, Serialize};
+use crate::topicmodel::dictionary::{Dictionary, DictionaryMut};
+use crate::topicmodel::dictionary::direction::{DirectionKind, DirectionTuple};
+use crate::topicmodel::language_hint::LanguageHint;
+use crate::topicmodel::reference::HashRef;
+use crate::topicmodel::vocabulary::{BasicVocabulary, Vocabulary};
+
+/// A single translation between a word in language A and a word in language B.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DictionaryEntry<T> {
+    pub word_a: T,
+    pub word_b: T,
+    pub direction: DirectionKind
+}
+
+impl<T> DictionaryEntry<T> {
+    pub fn new(word_a: T, word_b: T, direction: DirectionKind) -> Self {
+        Self { word_a, word_b, direction }
+    }
+}
+
+impl<T> From<DirectionTuple<(usize, HashRef<T>), (usize, HashRef<T>)>> for DictionaryEntry<T> where T: Clone {
+    fn from(value: DirectionTuple<(usize, HashRef<T>), (usize, HashRef<T>)>) -> Self {
+        Self::new(
+            T::clone(&value.a.1),
+            T::clone(&value.b.1),
+            value.direction
+        )
+    }
+}
+
+impl<T> Dictionary<T, Vocabulary<T>> where T: Eq + Hash {
+    /// Creates a dictionary with the languages `lang_a` and `lang_b` containing all `entries`.
+    pub fn from_entry_list<I>(entries: I, lang_a: Option<LanguageHint>, lang_b: Option<LanguageHint>) -> Self where I: IntoIterator<Item=DictionaryEntry<T>> {
+        let mut dictionary = Self::new_with(lang_a, lang_b);
+        dictionary.extend(entries);
+        dictionary
+    }
+}
+
+impl<T, V> Dictionary<T, V> where T: Eq + Hash + Clone, V: BasicVocabulary<T> {
+    /// Converts the dictionary to its entries.
+    pub fn into_entries(self) -> impl Iterator<Item=DictionaryEntry<T>> {
+        self.into_iter().map(DictionaryEntry::from)
+    }
+}
+
+impl<T> FromIterator<DictionaryEntry<T>> for Dictionary<T, Vocabulary<T>> where T: Eq + Hash {
+    fn from_iter<I: IntoIterator<Item=DictionaryEntry<T>>>(iter: I) -> Self {
+        Self::from_entry_list(iter, None, None)
+    }
+}
+
+impl<T> Extend<DictionaryEntry<T>> for Dictionary<T, Vocabulary<T>> where T: Eq + Hash {
+    fn extend<I: IntoIterator<Item=DictionaryEntry<T>>>(&mut self, iter: I) {
+        for entry in iter {
+            self.insert_entry(entry);
+        }
+    }
+}
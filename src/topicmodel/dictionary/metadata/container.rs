@@ -1,9 +1,10 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 use crate::topicmodel::dictionary::direction::{Language, A, B};
-use crate::topicmodel::dictionary::metadata::{Metadata, MetadataMutRef, MetadataRef};
+use crate::topicmodel::dictionary::metadata::{Metadata, MetadataMutRef, MetadataRef, SolvedMetadata};
 use crate::topicmodel::dictionary::metadata::typesafe_interner::{DefaultDictionaryOriginStringInterner, DefaultTagStringInterner};
-use crate::topicmodel::vocabulary::Vocabulary;
+use crate::topicmodel::vocabulary::{Vocabulary, VocabularyMut};
 
 /// Contains the metadata for the dictionary
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
@@ -152,6 +153,49 @@ impl MetadataContainer {
         unsafe { MetadataMutRef::new(ptr, targ.get_unchecked_mut(word_id)) }
     }
 
+    /// Merges `metadata` into the metadata of all `word_ids` in the language `L`.
+    /// The values are interned only once and the metadata is reserved up to the largest id.
+    pub fn apply_meta_to_ids<L: Language>(&mut self, word_ids: &[usize], metadata: &SolvedMetadata) {
+        let Some(max_id) = word_ids.iter().max().copied() else {
+            return
+        };
+
+        let associated_dictionaries = metadata.associated_dictionaries().map(|values| {
+            values.iter().map(|value| self.dictionary_interner.get_or_intern(value)).collect::<HashSet<_>>()
+        });
+        let subjects = metadata.subjects().map(|values| {
+            values.iter().map(|value| self.subject_interner.get_or_intern(value)).collect::<HashSet<_>>()
+        });
+        let unstemmed = metadata.unstemmed().map(|values| {
+            values.into_iter().map(|(word, origins)| {
+                let origins = origins.iter().map(|value| self.dictionary_interner.get_or_intern(value)).collect::<HashSet<_>>();
+                (self.unstemmed_voc.add(word), origins)
+            }).collect::<HashMap<_, _>>()
+        });
+
+        let targ = if L::LANG.is_a() {
+            &mut self.meta_a
+        } else {
+            &mut self.meta_b
+        };
+        if targ.len() <= max_id {
+            targ.resize(max_id + 1, Metadata::default());
+        }
+
+        for word_id in word_ids {
+            let meta = &mut targ[*word_id];
+            if let Some(ref associated_dictionaries) = associated_dictionaries {
+                unsafe { meta.add_all_associated_dictionaries(associated_dictionaries) }
+            }
+            if let Some(ref subjects) = subjects {
+                unsafe { meta.add_all_subjects(subjects) }
+            }
+            if let Some(ref unstemmed) = unstemmed {
+                meta.add_all_unstemmed(unstemmed)
+            }
+        }
+    }
+
     pub fn get_meta_ref<L: Language>(&self, word_id: usize) -> Option<MetadataRef> {
         Some(MetadataRef::new(self.get_meta::<L>(word_id)?, self))
     }
@@ -161,6 +205,14 @@ impl MetadataContainer {
         self.meta_b.resize(meta_b, Metadata::default());
     }
 
+    /// Copies the metadata of `other_word_id` in `other` to `word_id` in self.
+    /// Both containers have to share the same interners, e.g. by creating self with [MetadataContainer::copy_keep_vocebulary].
+    pub fn copy_meta_from<L: Language>(&mut self, word_id: usize, other: &MetadataContainer, other_word_id: usize) {
+        if let Some(meta) = other.get_meta::<L>(other_word_id) {
+            *self.get_or_init_meta::<L>(word_id).meta = meta.clone();
+        }
+    }
+
     pub fn copy_keep_vocebulary(&self) -> Self {
         Self {
             dictionary_interner: self.dictionary_interner.clone(),
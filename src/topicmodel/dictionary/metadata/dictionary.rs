@@ -1,12 +1,17 @@
 use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
+use std::io::{BufRead, Read, Write};
+use std::ops::{Deref, DerefMut};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable, DictionaryMut, DictionaryWithVocabulary, FromVoc};
-use crate::topicmodel::dictionary::direction::{AToB, BToA, Direction, DirectionKind, DirectionTuple, Invariant, Language, Translation, A, B};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryFilterable, DictionaryMut, DictionaryWithVocabulary, FromVoc, FromVocWithMeta};
+use crate::topicmodel::dictionary::direction::{AToB, BToA, Direction, DirectionKind, DirectionTuple, Invariant, Language, LanguageKind, Translation, A, B};
 use crate::topicmodel::dictionary::iterators::{DictIter, DictionaryWithMetaIterator};
-use crate::topicmodel::dictionary::metadata::{MetadataContainer, MetadataContainerWithDict, MetadataContainerWithDictMut, MetadataRef, SolvedMetadata};
+use crate::topicmodel::dictionary::metadata::{MetadataContainer, MetadataContainerWithDict, MetadataContainerWithDictMut, MetadataMutRef, MetadataRef, SolvedMetadata};
 use crate::topicmodel::language_hint::LanguageHint;
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::vocabulary::{BasicVocabulary, MappableVocabulary, SearchableVocabulary, Vocabulary, VocabularyMut};
@@ -79,6 +84,23 @@ impl<T, V> DictionaryWithMeta<T, V> {
     pub fn unstemmed(&self) -> &Vocabulary<String> {
         &self.metadata.unstemmed_voc
     }
+
+    /// The original forms of the word with `word_id` in the language `L` before the normalization,
+    /// as stored in the unstemmed metadata. The forms are unique and ordered by their first insertion
+    /// into the unstemmed vocabulary shared by all words, not by the insertion for this word.
+    /// A form already added for another word keeps its earlier position.
+    pub fn original_forms<L: Language>(&self, word_id: usize) -> Vec<&str> {
+        let Some(unstemmed) = self.metadata.get_meta::<L>(word_id).and_then(|meta| meta.unstemmed.get()) else {
+            return Vec::new()
+        };
+        unstemmed
+            .keys()
+            .copied()
+            .sorted()
+            .filter_map(|id| self.metadata.unstemmed_voc.get_value(id))
+            .map(|value| value.as_str())
+            .collect_vec()
+    }
 }
 
 
@@ -168,6 +190,24 @@ impl<T, V> DictionaryWithMeta<T, V> where V: VocabularyMut<T> + From<Option<Lang
     }
 }
 
+impl<T, V> DictionaryWithMeta<T, V> where T: Eq + Hash, V: VocabularyMut<T> + From<Option<LanguageHint>> {
+    /// See [Dictionary::prune_orphans], the metadata of the removed words is dropped.
+    pub fn prune_orphans(&mut self) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        fn remap_meta<M>(meta: &mut Vec<M>, remap: &[Option<usize>]) {
+            *meta = std::mem::take(meta)
+                .into_iter()
+                .enumerate()
+                .filter(|(id, _)| remap.get(*id).is_some_and(|value| value.is_some()))
+                .map(|(_, meta)| meta)
+                .collect();
+        }
+        let (remap_a, remap_b) = self.inner.prune_orphans();
+        remap_meta(&mut self.metadata.meta_a, &remap_a);
+        remap_meta(&mut self.metadata.meta_b, &remap_b);
+        (remap_a, remap_b)
+    }
+}
+
 impl<T, V> FromVoc<T, V> for DictionaryWithMeta<T, V> where V: BasicVocabulary<T> + Default, T: Hash + Eq  {
     fn from_voc(voc_a: V, voc_b: V) -> Self {
         Self::new(
@@ -185,6 +225,15 @@ impl<T, V> FromVoc<T, V> for DictionaryWithMeta<T, V> where V: BasicVocabulary<T
 }
 
 
+impl<T, V> FromVocWithMeta<T, V> for DictionaryWithMeta<T, V> where V: BasicVocabulary<T> + Default, T: Hash + Eq  {
+    fn from_voc_lang_with_meta<L: Language>(voc: V, other_lang: Option<LanguageHint>, metadata: &MetadataContainer) -> Self {
+        Self::new(
+            Dictionary::from_voc_lang::<L>(voc, other_lang),
+            metadata.copy_keep_vocebulary()
+        )
+    }
+}
+
 impl<T, V> Clone for DictionaryWithMeta<T, V> where V: Clone {
     fn clone(&self) -> Self {
         Self::new(self.inner.clone(), self.metadata.clone())
@@ -270,6 +319,105 @@ impl<T, V> DictionaryWithVocabulary<T, V> for  DictionaryWithMeta<T, V> where V:
         self.inner.word_to_id::<D, _>(id)
     }
 }
+
+/// The id and the metadata of a word in a [DictionaryWithMeta], see [DictionaryWithMeta::entry].
+pub struct WordEntry<'a> {
+    id: usize,
+    meta: MetadataMutRef<'a>
+}
+
+impl<'a> WordEntry<'a> {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn into_meta(self) -> MetadataMutRef<'a> {
+        self.meta
+    }
+}
+
+impl<'a> Deref for WordEntry<'a> {
+    type Target = MetadataMutRef<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.meta
+    }
+}
+
+impl<'a> DerefMut for WordEntry<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.meta
+    }
+}
+
+impl<T, V> DictionaryWithMeta<T, V> where T: Eq + Hash, V: VocabularyMut<T> {
+    /// Gets or inserts `word` in the language `L` and returns its id together with its metadata,
+    /// the metadata is created if necessary. The word is not translated by inserting it.
+    pub fn entry<L: Language>(&mut self, word: impl Into<T>) -> WordEntry {
+        let id = if L::LANG.is_a() {
+            self.inner.voc_a.add(word)
+        } else {
+            self.inner.voc_b.add(word)
+        };
+        WordEntry {
+            id,
+            meta: self.metadata.get_or_init_meta::<L>(id)
+        }
+    }
+}
+
+impl<T, V> DictionaryWithMeta<T, V> where V: BasicVocabulary<T> {
+    /// See [Dictionary::apply_translation_filter], the metadata is not changed.
+    pub fn apply_translation_filter<F>(&mut self, predicate: F) where F: Fn(&T, &T, DirectionKind) -> bool {
+        self.inner.apply_translation_filter(predicate)
+    }
+
+    /// Counts the translation pairs per subject, a pair is counted once for every subject of its words.
+    /// If `side` is set, only the subjects of the words in this language are considered.
+    pub fn subject_histogram(&self, side: Option<LanguageKind>) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        for DirectionTuple { a, b, direction: _ } in self.iter() {
+            let mut subjects = HashSet::new();
+            if !side.is_some_and(|side| side.is_b()) {
+                if let Some(found) = self.metadata.get_meta_ref::<A>(a).as_ref().and_then(|meta| meta.subjects().cloned()) {
+                    subjects.extend(found);
+                }
+            }
+            if !side.is_some_and(|side| side.is_a()) {
+                if let Some(found) = self.metadata.get_meta_ref::<B>(b).as_ref().and_then(|meta| meta.subjects().cloned()) {
+                    subjects.extend(found);
+                }
+            }
+            for subject in subjects {
+                *histogram.entry(subject.to_string()).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Merges `metadata` into the metadata of all `word_ids` in the language `L`, see [MetadataContainer::apply_meta_to_ids].
+    pub fn apply_meta_to_ids<L: Language>(&mut self, word_ids: &[usize], metadata: &SolvedMetadata) {
+        self.metadata.apply_meta_to_ids::<L>(word_ids, metadata)
+    }
+
+    /// Same as [DictionaryWithMeta::apply_translation_filter], but the metadata of both words is passed to `predicate`.
+    pub fn apply_translation_filter_with_meta<F>(&mut self, predicate: F) where F: Fn(&T, &T, DirectionKind, Option<MetadataRef>, Option<MetadataRef>) -> bool {
+        let voc_a = &self.inner.voc_a;
+        let voc_b = &self.inner.voc_b;
+        let metadata = &self.metadata;
+        let removed = Dictionary::<T, V>::collect_translations_to_remove(&self.inner.map_a_to_b, &self.inner.map_b_to_a, |a, b, direction| {
+            predicate(
+                voc_a.get_value(a).unwrap().as_ref(),
+                voc_b.get_value(b).unwrap().as_ref(),
+                direction,
+                metadata.get_meta_ref::<A>(a),
+                metadata.get_meta_ref::<B>(b)
+            )
+        });
+        self.inner.remove_translations(&removed);
+    }
+}
+
 impl<T, V> DictionaryMut<T, V> for  DictionaryWithMeta<T, V> where T: Eq + Hash, V: VocabularyMut<T> {
     fn set_language<L: Language>(&mut self, value: Option<LanguageHint>) -> Option<LanguageHint> {
         self.inner.set_language::<L>(value)
@@ -382,6 +530,138 @@ impl<T, V> DictionaryFilterable<T, V>  for DictionaryWithMeta<T, V> where T: Eq
     }
 }
 
+/// A single line of [DictionaryWithMeta::write_jsonl].
+#[derive(Serialize)]
+struct JsonlEntry<'a, T> {
+    id_a: usize,
+    word_a: &'a T,
+    meta_a: Option<SolvedMetadata>,
+    id_b: usize,
+    word_b: &'a T,
+    meta_b: Option<SolvedMetadata>,
+    direction: DirectionKind
+}
+
+impl<T, V> DictionaryWithMeta<T, V> where V: BasicVocabulary<T>, T: Serialize {
+    /// The number of lines after which [DictionaryWithMeta::write_jsonl] flushes the writer.
+    pub const JSONL_FLUSH_INTERVAL: usize = 10_000;
+
+    /// Writes every translation pair with the solved metadata as a JSON object on its own line to `writer`.
+    /// Returns the number of written lines.
+    pub fn write_jsonl(&self, mut writer: impl Write) -> std::io::Result<usize> {
+        let mut count = 0usize;
+        for DirectionTuple{a: (id_a, meta_a), b: (id_b, meta_b), direction} in self.iter_with_meta() {
+            let entry = JsonlEntry {
+                id_a,
+                word_a: self.id_to_word::<A>(id_a).expect("The word a has to exist!").as_ref(),
+                meta_a: meta_a.map(SolvedMetadata::from),
+                id_b,
+                word_b: self.id_to_word::<B>(id_b).expect("The word b has to exist!").as_ref(),
+                meta_b: meta_b.map(SolvedMetadata::from),
+                direction
+            };
+            serde_json::to_writer(&mut writer, &entry)?;
+            writer.write_all(b"\n")?;
+            count += 1;
+            if count % Self::JSONL_FLUSH_INTERVAL == 0 {
+                writer.flush()?;
+            }
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+}
+
+/// The prefix of the header line written by [DictionaryWithMeta::write_json].
+const CHECKSUM_HEADER_PREFIX: &str = "#ldatranslate-checksum";
+
+/// An error when reading a [DictionaryWithMeta] with [DictionaryWithMeta::read_json].
+#[derive(Debug, Error)]
+pub enum DictionaryReadError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("The checksum header \"{0}\" is malformed!")]
+    IllegalChecksumHeader(String),
+    #[error("Expected {expected_length} bytes with the checksum {expected:08x} but got {actual_length} bytes with the checksum {actual:08x}!")]
+    ChecksumMismatch {
+        expected: u32,
+        expected_length: u64,
+        actual: u32,
+        actual_length: u64
+    },
+}
+
+/// Writes `value` as JSON to `writer`. If `with_checksum` is set, the JSON is preceded by a header
+/// line containing the length and the crc32 checksum of the JSON bytes.
+pub(crate) fn write_json_with_checksum<S: Serialize + ?Sized>(value: &S, mut writer: impl Write, with_checksum: bool) -> Result<(), serde_json::Error> {
+    if with_checksum {
+        let content = serde_json::to_vec(value)?;
+        let mut crc = flate2::Crc::new();
+        crc.update(&content);
+        writeln!(writer, "{CHECKSUM_HEADER_PREFIX} crc32={:08x} len={}", crc.sum(), content.len()).map_err(serde_json::Error::io)?;
+        writer.write_all(&content).map_err(serde_json::Error::io)?;
+    } else {
+        serde_json::to_writer(&mut writer, value)?;
+    }
+    writer.flush().map_err(serde_json::Error::io)
+}
+
+/// Parses a header line written by [write_json_with_checksum], returns the checksum and the length.
+fn parse_checksum_header(line: &str) -> Option<(u32, u64)> {
+    let mut parts = line.trim_end().strip_prefix(CHECKSUM_HEADER_PREFIX)?.split_whitespace();
+    let checksum = u32::from_str_radix(parts.next()?.strip_prefix("crc32=")?, 16).ok()?;
+    let length = parts.next()?.strip_prefix("len=")?.parse().ok()?;
+    if parts.next().is_some() {
+        return None
+    }
+    Some((checksum, length))
+}
+
+/// Reads a JSON value written by [write_json_with_checksum]. If the value has a checksum header,
+/// the content is verified before it is deserialized.
+pub(crate) fn read_json_with_checksum<D: DeserializeOwned>(mut reader: impl BufRead) -> Result<D, DictionaryReadError> {
+    if !reader.fill_buf()?.starts_with(b"#") {
+        return Ok(serde_json::from_reader(reader)?)
+    }
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    let (expected, expected_length) = parse_checksum_header(&header).ok_or_else(|| DictionaryReadError::IllegalChecksumHeader(header.trim_end().to_string()))?;
+    // The header is not trusted before the checksum is verified, the preallocation is capped.
+    let mut content = Vec::with_capacity(usize::try_from(expected_length).unwrap_or_default().min(1 << 20));
+    reader.read_to_end(&mut content)?;
+    let mut crc = flate2::Crc::new();
+    crc.update(&content);
+    if crc.sum() != expected || content.len() as u64 != expected_length {
+        return Err(
+            DictionaryReadError::ChecksumMismatch {
+                expected,
+                expected_length,
+                actual: crc.sum(),
+                actual_length: content.len() as u64
+            }
+        )
+    }
+    Ok(serde_json::from_slice(&content)?)
+}
+
+impl<T, V> DictionaryWithMeta<T, V> where V: Serialize, T: Serialize {
+    /// Writes the dictionary as JSON to `writer`. If `with_checksum` is set, a crc32 checksum of the
+    /// JSON is embedded, allowing [DictionaryWithMeta::read_json] to detect corrupted or truncated files.
+    pub fn write_json(&self, writer: impl Write, with_checksum: bool) -> Result<(), serde_json::Error> {
+        write_json_with_checksum(self, writer, with_checksum)
+    }
+}
+
+impl<T, V> DictionaryWithMeta<T, V> where V: DeserializeOwned, T: DeserializeOwned + Hash + Eq {
+    /// Reads a dictionary written by [DictionaryWithMeta::write_json]. Fails with
+    /// [DictionaryReadError::ChecksumMismatch] if an embedded checksum does not match the content.
+    pub fn read_json(reader: impl BufRead) -> Result<Self, DictionaryReadError> {
+        read_json_with_checksum(reader)
+    }
+}
+
 impl<T: Display, V: BasicVocabulary<T>> Display for DictionaryWithMeta<T, V> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(&self.inner, f)?;
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use itertools::Itertools;
 use pyo3::{pyclass, pymethods, FromPyObject, IntoPy, PyObject, Python};
+use serde::{Deserialize, Serialize};
 use string_interner::DefaultSymbol;
 use crate::topicmodel::dictionary::metadata::container::MetadataContainer;
 use crate::topicmodel::dictionary::metadata::{Metadata, MetadataRef};
@@ -98,7 +99,7 @@ impl<'a> MetadataMutRef<'a> {
 }
 
 /// A completely memory save copy of some [Metadata]
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[pyclass]
 pub struct SolvedMetadata {
     associated_dictionaries: Option<Vec<String>>,
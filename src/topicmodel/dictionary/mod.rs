@@ -17,13 +17,20 @@
 pub mod metadata;
 pub mod direction;
 pub mod iterators;
+pub mod quality;
 mod traits;
 mod dictionary;
+mod entry;
+mod sorted_view;
 
 pub use traits::*;
 
+pub use entry::*;
+
 pub use dictionary::*;
 
+pub use sorted_view::*;
+
 pub use metadata::dictionary::*;
 
 #[macro_export]
@@ -75,10 +82,156 @@ macro_rules! dict {
 
 #[cfg(test)]
 mod test {
-    use crate::topicmodel::dictionary::{BasicDictionaryWithMeta, DictionaryMut, DictionaryWithMeta, DictionaryWithVocabulary, FromVoc};
-    use crate::topicmodel::dictionary::direction::{A, B, DirectionTuple, Invariant};
+    use std::collections::HashSet;
+    use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithMeta, BasicDictionaryWithVocabulary, Dictionary, DictionaryEntry, DictionaryMut, DictionaryReadError, DictionaryWithMeta, DictionaryWithVocabulary, FromVoc};
+    use crate::topicmodel::dictionary::direction::{A, AToB, B, BToA, DirectionKind, DirectionTuple, Invariant, LanguageKind};
+    use crate::topicmodel::language_hint::LanguageHint;
     use crate::topicmodel::dictionary::metadata::SolvedMetadata;
-    use crate::topicmodel::vocabulary::{SearchableVocabulary, Vocabulary};
+    use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary, Vocabulary, VocabularyMut};
+
+    /// An empty dictionary from english to german.
+    fn create_en_de_dictionary() -> DictionaryWithMeta<String, Vocabulary<String>> {
+        DictionaryWithMeta::new_with(
+            Some(LanguageHint::new("en")),
+            Some(LanguageHint::new("de"))
+        )
+    }
+
+    #[test]
+    fn can_find_polysemous_words(){
+        let (voc_a, voc_b, dict) = crate::translate::test::create_test_data();
+        let plane = dict.word_to_id::<AToB, _>("plane").unwrap();
+        let aircraft = dict.word_to_id::<AToB, _>("aircraft").unwrap();
+
+        let found = dict.find_polysemous_words_a(5);
+        assert_eq!(vec![plane, aircraft], found.iter().map(|value| value.0).collect::<Vec<_>>());
+        assert_eq!(5, found[1].1);
+        assert!(found[0].1 > found[1].1);
+
+        let most = dict.most_polysemous_a(1);
+        assert_eq!(1, most.len());
+        assert_eq!(plane, most[0].0);
+        assert_eq!(voc_a.len(), dict.find_polysemous_words_a(1).len());
+
+        let tragflaeche = dict.word_to_id::<BToA, _>("Tragfläche").unwrap();
+        assert_eq!(tragflaeche, dict.most_polysemous::<BToA>(1)[0].0);
+        assert!(dict.find_polysemous_words::<BToA>(voc_b.len()).is_empty());
+    }
+
+    #[test]
+    fn batch_translation_equals_single_translation(){
+        let (voc_a, _, dict) = crate::translate::test::create_test_data();
+        let mut word_ids = voc_a.ids().collect::<Vec<_>>();
+        word_ids.push(voc_a.len() + 10);
+        assert_eq!(
+            word_ids.iter().map(|word_id| dict.translate_id_to_ids::<AToB>(*word_id)).collect::<Vec<_>>(),
+            dict.translate_words_batch_a_to_b(&word_ids)
+        );
+
+        let words = vec!["plane", "wing", "helicopter"];
+        let batch = dict.translate_words_a_to_b(&words);
+        assert_eq!(words.len(), batch.len());
+        for (word, translations) in words.iter().zip(batch.iter()) {
+            assert_eq!(
+                dict.translate_value_to_values::<AToB, _>(*word).map(|values| values.into_iter().map(|value| value.as_str()).collect::<Vec<_>>()),
+                translations.as_ref().map(|values| values.iter().map(|value| value.as_str()).collect::<Vec<_>>())
+            );
+        }
+        assert!(batch[2].is_none());
+    }
+
+    #[test]
+    fn can_iter_distinct_translation_pairs(){
+        let (_, _, dict) = crate::translate::test::create_test_data();
+        let pairs = dict.iter_distinct_translation_pairs().collect::<Vec<_>>();
+        let unique = pairs.iter().map(|(a, b, _)| (*a, *b)).collect::<HashSet<_>>();
+        assert_eq!(unique.len(), pairs.len());
+        assert_eq!(
+            dict.iter().map(|value| (value.a, value.b, value.direction)).collect::<HashSet<_>>(),
+            pairs.iter().cloned().collect::<HashSet<_>>()
+        );
+
+        let mut dict = Dictionary::from_entry_list(
+            vec![
+                DictionaryEntry::new("plane".to_string(), "Flugzeug".to_string(), DirectionKind::Invariant),
+                DictionaryEntry::new("plane".to_string(), "Ebene".to_string(), DirectionKind::AToB),
+                DictionaryEntry::new("aircraft".to_string(), "Flugzeug".to_string(), DirectionKind::BToA),
+            ],
+            None,
+            None
+        );
+        let plane = dict.word_to_id::<AToB, _>("plane").unwrap();
+        let aircraft = dict.word_to_id::<AToB, _>("aircraft").unwrap();
+        let flugzeug = dict.word_to_id::<BToA, _>("Flugzeug").unwrap();
+        let ebene = dict.word_to_id::<BToA, _>("Ebene").unwrap();
+        let pairs = dict.iter_distinct_translation_pairs().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (plane, flugzeug, DirectionKind::Invariant),
+                (plane, ebene, DirectionKind::AToB),
+                (aircraft, flugzeug, DirectionKind::BToA),
+            ],
+            pairs
+        );
+        dict.insert_entry(DictionaryEntry::new("aircraft".to_string(), "Flugzeug".to_string(), DirectionKind::AToB));
+        assert_eq!(
+            Some((aircraft, flugzeug, DirectionKind::Invariant)),
+            dict.iter_distinct_translation_pairs().find(|(a, _, _)| *a == aircraft)
+        );
+    }
+
+    #[test]
+    fn can_roundtrip_entries(){
+        let (_, _, dict) = crate::translate::test::create_test_data();
+        let entries = dict.clone().into_entries().collect::<Vec<_>>();
+        assert!(entries.contains(&DictionaryEntry::new("plane".to_string(), "Flugzeug".to_string(), DirectionKind::Invariant)));
+
+        let rebuilt: Dictionary<String, Vocabulary<String>> = entries.iter().cloned().collect();
+        assert_eq!(dict.voc_a().len(), rebuilt.voc_a().len());
+        assert_eq!(dict.voc_b().len(), rebuilt.voc_b().len());
+        assert_eq!(
+            entries.iter().cloned().collect::<HashSet<_>>(),
+            rebuilt.into_entries().collect::<HashSet<_>>()
+        );
+
+        let mut dict = Dictionary::from_entry_list(
+            vec![
+                DictionaryEntry::new("plane".to_string(), "Flugzeug".to_string(), DirectionKind::AToB),
+                DictionaryEntry::new("plane".to_string(), "Ebene".to_string(), DirectionKind::BToA),
+            ],
+            Some("en".into()),
+            Some("de".into())
+        );
+        assert_eq!(Some(&LanguageHint::new("en")), dict.language::<A>());
+        let plane = dict.word_to_id::<AToB, _>("plane").unwrap();
+        assert_eq!(1, dict.translate_id_to_ids::<AToB>(plane).unwrap().len());
+        let ebene = dict.word_to_id::<BToA, _>("Ebene").unwrap();
+        assert_eq!(&vec![plane], dict.translate_id_to_ids::<BToA>(ebene).unwrap());
+        dict.insert_entry(DictionaryEntry::new("aircraft".to_string(), "Flugzeug".to_string(), DirectionKind::Invariant));
+        assert_eq!(2, dict.voc_a().len());
+    }
+
+    #[test]
+    fn can_find_synonym_candidates(){
+        let (_, _, dict) = crate::translate::test::create_test_data();
+        let plane = dict.word_to_id::<AToB, _>("plane").unwrap();
+        let aircraft = dict.word_to_id::<AToB, _>("aircraft").unwrap();
+        let airplane = dict.word_to_id::<AToB, _>("airplane").unwrap();
+        let wing = dict.word_to_id::<AToB, _>("wing").unwrap();
+
+        assert_eq!(1.0, dict.jaccard_similarity_a(plane, plane));
+        // Flugzeug and Flieger of 13 distinct translations
+        assert_eq!(2.0 / 13.0, dict.jaccard_similarity_a(plane, aircraft));
+        assert_eq!(dict.jaccard_similarity_a(plane, aircraft), dict.jaccard_similarity_a(aircraft, plane));
+        assert!(dict.jaccard_similarity_a(plane, aircraft) > dict.jaccard_similarity_a(plane, wing));
+        assert_eq!(0.0, dict.jaccard_similarity_a(plane, usize::MAX));
+
+        let candidates = dict.find_synonym_candidates_a(aircraft, 0.15);
+        assert_eq!(airplane, candidates[0].0);
+        assert!(candidates.iter().any(|(word_id, _)| *word_id == plane));
+        assert!(candidates.iter().all(|(word_id, similarity)| *word_id != aircraft && *similarity >= 0.15));
+        assert!(dict.find_synonym_candidates_a(aircraft, 1.1).is_empty());
+    }
 
     #[test]
     fn can_create_with_meta(){
@@ -215,4 +368,292 @@ mod test {
             )
         }
     }
+
+    #[test]
+    fn can_write_jsonl(){
+        let mut dict = create_en_de_dictionary();
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.insert::<AToB>("plane", "Ebene");
+        let DirectionTuple{ a, b: _, direction: _ } = dict.insert::<BToA>("aircraft", "Flugzeug");
+        dict.metadata.get_or_init_meta::<A>(a).push_associated_dictionary("DictA");
+
+        let mut out = Vec::new();
+        let written = dict.write_jsonl(&mut out).unwrap();
+        let lines = String::from_utf8(out).unwrap().lines().map(|line| line.to_string()).collect::<Vec<_>>();
+        assert_eq!(dict.iter().count(), written);
+        assert_eq!(written, lines.len());
+        for line in lines.iter() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value["word_a"].is_string());
+            assert!(value["word_b"].is_string());
+        }
+        assert!(lines.iter().any(|line| line.contains("\"aircraft\"") && line.contains("DictA")));
+    }
+
+    #[test]
+    fn checksum_detects_corrupted_dictionaries(){
+        let mut dict = create_en_de_dictionary();
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.insert::<AToB>("plane", "Ebene");
+        let DirectionTuple{ a, b: _, direction: _ } = dict.insert::<BToA>("aircraft", "Flugzeug");
+        dict.metadata.get_or_init_meta::<A>(a).push_associated_dictionary("DictA");
+
+        let mut plain = Vec::new();
+        dict.write_json(&mut plain, false).unwrap();
+        let reloaded = DictionaryWithMeta::<String, Vocabulary<String>>::read_json(plain.as_slice()).unwrap();
+        assert_eq!(dict.to_string(), reloaded.to_string());
+
+        let mut out = Vec::new();
+        dict.write_json(&mut out, true).unwrap();
+        let reloaded = DictionaryWithMeta::<String, Vocabulary<String>>::read_json(out.as_slice()).unwrap();
+        assert_eq!(dict.to_string(), reloaded.to_string());
+
+        let position = out.iter().rposition(|value| *value == b'F').unwrap();
+        let mut flipped = out.clone();
+        flipped[position] = b'f';
+        assert!(matches!(
+            DictionaryWithMeta::<String, Vocabulary<String>>::read_json(flipped.as_slice()),
+            Err(DictionaryReadError::ChecksumMismatch{..})
+        ));
+
+        let truncated = &out[..out.len() - 10];
+        assert!(matches!(
+            DictionaryWithMeta::<String, Vocabulary<String>>::read_json(truncated),
+            Err(DictionaryReadError::ChecksumMismatch{..})
+        ));
+    }
+
+    #[test]
+    fn can_find_connected_components(){
+        let mut dict = Dictionary::<String, Vocabulary<String>>::new();
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.insert::<AToB>("aircraft", "Flugzeug");
+        dict.insert::<BToA>("aircraft", "Luftfahrzeug");
+        dict.insert::<Invariant>("fever", "Fieber");
+        dict.insert::<Invariant>("temperature", "Fieber");
+        dict.voc_a.add("isolated".to_string());
+
+        let components = dict.connected_components();
+        assert_eq!(3, components.len());
+
+        let a = |word: &str| (dict.voc_a().get_id(word).unwrap(), LanguageKind::A);
+        let b = |word: &str| (dict.voc_b().get_id(word).unwrap(), LanguageKind::B);
+        assert_eq!(vec![a("plane"), a("aircraft"), b("Flugzeug"), b("Luftfahrzeug")], components[0]);
+        assert_eq!(vec![a("fever"), a("temperature"), b("Fieber")], components[1]);
+        assert_eq!(vec![a("isolated")], components[2]);
+        assert_eq!(4, dict.largest_component());
+
+        assert_eq!(0, Dictionary::<String, Vocabulary<String>>::new().largest_component());
+    }
+
+    #[test]
+    fn can_recover_the_original_forms_of_lemmatized_words(){
+        let mut dict = create_en_de_dictionary();
+        let DirectionTuple{ a: fly, b: fliegen, direction: _ } = dict.insert::<Invariant>("fly", "fliegen");
+        let DirectionTuple{ a: plane, b: _, direction: _ } = dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.metadata.set_unstemmed_words_for::<A>(fly, ["flies", "flew", "flies", "flown"].as_slice());
+        dict.metadata.set_unstemmed_word_for::<B>(fliegen, "flog");
+        dict.metadata.set_unstemmed_word_for::<A>(plane, "planes");
+        dict.metadata.set_unstemmed_word_for::<A>(fly, "flying");
+        dict.metadata.set_unstemmed_word_for::<A>(fly, "flew");
+
+        assert_eq!(vec!["flies", "flew", "flown", "flying"], dict.original_forms::<A>(fly));
+        assert_eq!(vec!["flog"], dict.original_forms::<B>(fliegen));
+        assert_eq!(vec!["planes"], dict.original_forms::<A>(plane));
+        assert!(dict.original_forms::<B>(dict.voc_b().get_id("Flugzeug").unwrap()).is_empty());
+        assert!(dict.original_forms::<A>(100).is_empty());
+
+        // "flying" was already added for "fly" and precedes "flights".
+        let DirectionTuple{ a: flight, b: _, direction: _ } = dict.insert::<Invariant>("flight", "Flug");
+        dict.metadata.set_unstemmed_words_for::<A>(flight, ["flights", "flying"].as_slice());
+        assert_eq!(vec!["flying", "flights"], dict.original_forms::<A>(flight));
+        assert_eq!(vec!["flies", "flew", "flown", "flying"], dict.original_forms::<A>(fly));
+    }
+
+    #[test]
+    fn prune_orphans_removes_words_without_translations(){
+        let mut dict = create_en_de_dictionary();
+        dict.insert::<Invariant>("plane", "Ebene");
+        dict.insert::<Invariant>("airplane", "Flugzeug");
+        let DirectionTuple{ a: aircraft, b: _, direction: _ } = dict.insert::<AToB>("aircraft", "Flugzeug");
+        dict.metadata.get_or_init_meta::<A>(aircraft).push_associated_dictionary("DictA");
+        let orphan = dict.entry::<A>("orphan").id();
+        dict.metadata.get_or_init_meta::<A>(orphan).push_associated_dictionary("DictB");
+        dict.apply_translation_filter(|a, _, _| a != "plane");
+
+        let (remap_a, remap_b) = dict.prune_orphans();
+        assert_eq!(vec![None, Some(0), Some(1), None], remap_a);
+        assert_eq!(vec![None, Some(0)], remap_b);
+
+        assert_eq!(2, dict.voc_a().len());
+        assert_eq!(1, dict.voc_b().len());
+        assert_eq!(Some(0), dict.voc_a().get_id("airplane"));
+        assert_eq!(Some(1), dict.voc_a().get_id("aircraft"));
+        assert_eq!(Some(0), dict.voc_b().get_id("Flugzeug"));
+        assert_eq!(&vec![0], dict.translate_id_to_ids::<AToB>(1).unwrap());
+        assert_eq!(&vec![0], dict.translate_id_to_ids::<BToA>(0).unwrap());
+        assert_eq!(
+            Some(vec!["DictA".to_string()]),
+            dict.metadata.get_meta_ref::<A>(1).map(SolvedMetadata::from).and_then(|meta| meta.associated_dictionaries())
+        );
+        assert!(dict.metadata.get_meta_ref::<A>(2).is_none());
+    }
+
+    #[test]
+    fn sorted_view_orders_translations_by_frequency(){
+        let mut dict = Dictionary::<String, Vocabulary<String>>::new();
+        dict.insert::<Invariant>("plane", "Ebene");
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.insert::<AToB>("plane", "Tragfläche");
+        dict.insert::<Invariant>("aircraft", "Flugzeug");
+
+        let plane = dict.voc_a().get_id("plane").unwrap();
+        let aircraft = dict.voc_a().get_id("aircraft").unwrap();
+        let ebene = dict.voc_b().get_id("Ebene").unwrap();
+        let flugzeug = dict.voc_b().get_id("Flugzeug").unwrap();
+        let tragflaeche = dict.voc_b().get_id("Tragfläche").unwrap();
+
+        let mut freq_a = vec![0; dict.voc_a().len()];
+        freq_a[plane] = 3;
+        freq_a[aircraft] = 7;
+        let mut freq_b = vec![0; dict.voc_b().len()];
+        freq_b[ebene] = 1;
+        freq_b[flugzeug] = 10;
+        freq_b[tragflaeche] = 5;
+
+        let view = dict.sorted_view_by_frequency(&freq_a, &freq_b);
+        assert_eq!(&vec![flugzeug, tragflaeche, ebene], view.translate_id_to_ids::<AToB>(plane).unwrap());
+        assert_eq!(&vec![aircraft, plane], view.translate_id_to_ids::<BToA>(flugzeug).unwrap());
+        assert_eq!(
+            vec!["Flugzeug", "Tragfläche", "Ebene"],
+            view.translate_value_to_values::<AToB, _>("plane").unwrap().iter().map(|value| value.as_str()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(&vec![ebene, flugzeug, tragflaeche], dict.translate_id_to_ids::<AToB>(plane).unwrap());
+        assert_eq!(&vec![plane, aircraft], dict.translate_id_to_ids::<BToA>(flugzeug).unwrap());
+
+        let switched = view.switch_languages();
+        assert_eq!(Some(flugzeug), switched.voc_a().get_id("Flugzeug"));
+        assert_eq!(&vec![aircraft, plane], switched.translate_id_to_ids::<AToB>(flugzeug).unwrap());
+    }
+
+    #[test]
+    fn can_filter_translations(){
+        let mut dict = Dictionary::<String, Vocabulary<String>>::new();
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        dict.insert::<Invariant>("go", "Flugzeug");
+        dict.insert::<AToB>("plane", "Ab");
+        dict.insert::<BToA>("at", "Ebene");
+        dict.insert::<BToA>("plane", "Ebene");
+
+        dict.apply_translation_filter(|a, b, _| a.len() >= 3 && b.len() >= 3);
+
+        let plane = dict.voc_a().get_id("plane").unwrap();
+        let go = dict.voc_a().get_id("go").unwrap();
+        let at = dict.voc_a().get_id("at").unwrap();
+        let flugzeug = dict.voc_b().get_id("Flugzeug").unwrap();
+        let ab = dict.voc_b().get_id("Ab").unwrap();
+        let ebene = dict.voc_b().get_id("Ebene").unwrap();
+
+        assert_eq!(&vec![flugzeug], dict.translate_id_to_ids::<AToB>(plane).unwrap());
+        assert!(dict.translate_id_to_ids::<AToB>(go).unwrap().is_empty());
+        assert_eq!(&vec![plane], dict.translate_id_to_ids::<BToA>(flugzeug).unwrap());
+        assert!(dict.translate_id_to_ids::<BToA>(ab).map_or(true, |value| value.is_empty()));
+        assert_eq!(&vec![plane], dict.translate_id_to_ids::<BToA>(ebene).unwrap());
+        assert!(!dict.iter().any(|DirectionTuple{a, b: _, direction: _}| a == at || a == go));
+        assert_eq!(2, dict.iter().count());
+
+        let mut dict = create_en_de_dictionary();
+        dict.insert::<Invariant>("plane", "Flugzeug");
+        let DirectionTuple{ a, b: _, direction: _ } = dict.insert::<Invariant>("aircraft", "Flugzeug");
+        dict.metadata.get_or_init_meta::<A>(a).push_associated_dictionary("DictA");
+
+        dict.apply_translation_filter_with_meta(|_, _, direction, meta_a, meta_b| {
+            assert_eq!(DirectionKind::Invariant, direction);
+            assert!(meta_b.is_none());
+            meta_a.is_some_and(|meta| meta.has_associated_dictionary("DictA"))
+        });
+        assert!(dict.translate_value_to_ids::<AToB, str>("aircraft").is_some_and(|value| value.len() == 1));
+        assert!(dict.translate_value_to_ids::<AToB, str>("plane").is_some_and(|value| value.is_empty()));
+        assert_eq!(1, dict.map_b_to_a().iter().flatten().count());
+    }
+
+    #[test]
+    fn can_apply_meta_to_many_ids(){
+        let mut dict = create_en_de_dictionary();
+        let plane = dict.insert::<Invariant>("plane", "Flugzeug").a;
+        let aircraft = dict.insert::<Invariant>("aircraft", "Flugzeug").a;
+        let wing = dict.insert::<Invariant>("wing", "Tragfläche").a;
+        dict.metadata.get_or_init_meta::<A>(plane).push_associated_dictionary("DictA");
+
+        let glossary = SolvedMetadata::new(
+            Some(vec!["Glossary".to_string()]),
+            Some(vec!["aviation".to_string()]),
+            None
+        );
+        dict.apply_meta_to_ids::<A>(&[plane, aircraft], &glossary);
+
+        for word_id in [plane, aircraft] {
+            let meta = dict.metadata.get_meta_ref::<A>(word_id).unwrap();
+            assert!(meta.has_subject("aviation"));
+            assert!(meta.has_associated_dictionary("Glossary"));
+        }
+        assert!(dict.metadata.get_meta_ref::<A>(plane).unwrap().has_associated_dictionary("DictA"));
+        assert!(!dict.metadata.get_meta_ref::<A>(aircraft).unwrap().has_associated_dictionary("DictA"));
+        assert!(dict.metadata.get_meta_ref::<A>(wing).map_or(true, |meta| !meta.has_subject("aviation")));
+        assert!(dict.metadata.get_meta_ref::<B>(0).map_or(true, |meta| !meta.has_subject("aviation")));
+    }
+
+    #[test]
+    fn can_count_pairs_per_subject(){
+        let mut dict = create_en_de_dictionary();
+        let DirectionTuple{ a: plane, b: flugzeug, direction: _ } = dict.insert::<Invariant>("plane", "Flugzeug");
+        let aircraft = dict.insert::<Invariant>("aircraft", "Flugzeug").a;
+        let DirectionTuple{ a: _, b: ebene, direction: _ } = dict.insert::<Invariant>("plane", "Ebene");
+        dict.insert::<Invariant>("wing", "Tragfläche");
+        dict.metadata.set_subjects_for::<A>(plane, ["aviation", "math"].as_slice());
+        dict.metadata.set_subject_for::<A>(aircraft, "aviation");
+        dict.metadata.set_subject_for::<B>(flugzeug, "aviation");
+        dict.metadata.set_subject_for::<B>(ebene, "math");
+
+        let both = dict.subject_histogram(None);
+        assert_eq!(Some(&3), both.get("aviation"));
+        assert_eq!(Some(&2), both.get("math"));
+        assert_eq!(2, both.len());
+
+        let source = dict.subject_histogram(Some(LanguageKind::A));
+        assert_eq!(Some(&3), source.get("aviation"));
+        assert_eq!(Some(&2), source.get("math"));
+
+        let target = dict.subject_histogram(Some(LanguageKind::B));
+        assert_eq!(Some(&2), target.get("aviation"));
+        assert_eq!(Some(&1), target.get("math"));
+    }
+
+    #[test]
+    fn can_insert_words_with_entry(){
+        let mut dict = create_en_de_dictionary();
+        let DirectionTuple{ a: _, b, direction: _ } = dict.insert::<Invariant>("plane", "Flugzeug");
+
+        let mut entry = dict.entry::<B>("Flugzeug");
+        assert_eq!(b, entry.id());
+        entry.push_subject("aviation");
+        drop(entry);
+
+        let mut entry = dict.entry::<A>("aircraft");
+        let aircraft = entry.id();
+        entry.push_subject("aviation");
+        entry.push_associated_dictionary("DictA");
+        drop(entry);
+
+        assert_eq!(Some(aircraft), dict.voc_a().get_id("aircraft"));
+        assert!(dict.translate_id_to_ids::<AToB>(aircraft).map_or(true, |value| value.is_empty()));
+        let meta = dict.metadata.get_meta_ref::<A>(aircraft).unwrap();
+        assert!(meta.has_subject("aviation"));
+        assert!(meta.has_associated_dictionary("DictA"));
+        assert!(dict.metadata.get_meta_ref::<B>(b).unwrap().has_subject("aviation"));
+        assert_eq!(b, dict.entry::<B>("Flugzeug").id());
+        assert_eq!(2, dict.voc_a().len());
+        assert_eq!(1, dict.voc_b().len());
+    }
 }
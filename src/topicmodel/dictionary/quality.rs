@@ -0,0 +1,347 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use crate::topicmodel::dictionary::direction::{AToB, BToA, Translation};
+use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithVocabulary, DictionaryWithVocabulary};
+use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary};
+
+/// The result of a round trip A → B → A over a vocabulary.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct RoundtripReport {
+    /// The number of checked words.
+    pub word_count: usize,
+    /// The number of words with at least one translation to B.
+    pub translatable_count: usize,
+    /// The number of words that are recovered after translating back to A.
+    pub recovered_count: usize,
+    /// The average number of translations to B of the translatable words.
+    pub average_translations_b: f64,
+    /// The average number of distinct words in A after translating back.
+    pub average_retranslations_a: f64,
+}
+
+impl RoundtripReport {
+    /// The fraction of the checked words that are recovered by the round trip.
+    pub fn coverage(&self) -> f64 {
+        if self.word_count == 0 {
+            0.0
+        } else {
+            self.recovered_count as f64 / self.word_count as f64
+        }
+    }
+}
+
+impl Display for RoundtripReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RoundtripReport(coverage: {:.4}, recovered: {}/{}, translatable: {}, avg_b: {:.2}, avg_a: {:.2})",
+            self.coverage(),
+            self.recovered_count,
+            self.word_count,
+            self.translatable_count,
+            self.average_translations_b,
+            self.average_retranslations_a
+        )
+    }
+}
+
+/// Translates every word in `voc_a` to B and back to A and checks if the original word reappears.
+pub(crate) fn roundtrip_coverage<D, T, V>(dictionary: &D, voc_a: &impl BasicVocabulary<T>) -> RoundtripReport
+where
+    D: DictionaryWithVocabulary<T, V> + ?Sized,
+    T: Eq + Hash,
+    V: BasicVocabulary<T> + SearchableVocabulary<T>
+{
+    let mut report = RoundtripReport::default();
+    let mut sum_b = 0usize;
+    let mut sum_a = 0usize;
+    for word in voc_a.iter() {
+        report.word_count += 1;
+        let Some(id_a) = dictionary.word_to_id::<AToB, T>(word.as_ref()) else {
+            continue
+        };
+        let Some(translations) = dictionary.translate_id_to_ids::<AToB>(id_a) else {
+            continue
+        };
+        if translations.is_empty() {
+            continue
+        }
+        report.translatable_count += 1;
+        sum_b += translations.len();
+        let retranslated = translations
+            .iter()
+            .filter_map(|id_b| dictionary.translate_id_to_ids::<BToA>(*id_b))
+            .flatten()
+            .copied()
+            .collect::<HashSet<_>>();
+        sum_a += retranslated.len();
+        if retranslated.contains(&id_a) {
+            report.recovered_count += 1;
+        }
+    }
+    if report.translatable_count > 0 {
+        report.average_translations_b = sum_b as f64 / report.translatable_count as f64;
+        report.average_retranslations_a = sum_a as f64 / report.translatable_count as f64;
+    }
+    report
+}
+
+/// The coverage of the tokens of a corpus by the vocabulary A of a dictionary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageReport {
+    /// The number of distinct tokens in the corpus.
+    pub type_count: usize,
+    /// The number of distinct tokens in vocabulary A.
+    pub covered_type_count: usize,
+    /// The number of token occurrences in the corpus.
+    pub token_count: usize,
+    /// The number of token occurrences in vocabulary A.
+    pub covered_token_count: usize,
+    /// The number of covered types per frequency bucket, bucket `i` holds frequencies in `[2^i, 2^(i+1))`.
+    pub covered_frequency_histogram: Vec<usize>,
+    /// The number of uncovered types per frequency bucket, bucket `i` holds frequencies in `[2^i, 2^(i+1))`.
+    pub uncovered_frequency_histogram: Vec<usize>,
+    uncovered_types: Vec<String>,
+}
+
+impl CoverageReport {
+    /// The fraction of the distinct tokens that are in vocabulary A.
+    pub fn type_coverage(&self) -> f64 {
+        if self.type_count == 0 {
+            0.0
+        } else {
+            self.covered_type_count as f64 / self.type_count as f64
+        }
+    }
+
+    /// The fraction of the token occurrences that are in vocabulary A.
+    pub fn token_coverage(&self) -> f64 {
+        if self.token_count == 0 {
+            0.0
+        } else {
+            self.covered_token_count as f64 / self.token_count as f64
+        }
+    }
+
+    /// The distinct tokens that are not in vocabulary A, the most frequent first.
+    pub fn uncovered_types(&self) -> Vec<String> {
+        self.uncovered_types.clone()
+    }
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CoverageReport(type_coverage: {:.4}, types: {}/{}, token_coverage: {:.4}, tokens: {}/{})",
+            self.type_coverage(),
+            self.covered_type_count,
+            self.type_count,
+            self.token_coverage(),
+            self.covered_token_count,
+            self.token_count
+        )
+    }
+}
+
+fn increment_bucket(histogram: &mut Vec<usize>, frequency: usize) {
+    let bucket = frequency.ilog2() as usize;
+    if histogram.len() <= bucket {
+        histogram.resize(bucket + 1, 0);
+    }
+    histogram[bucket] += 1;
+}
+
+/// Counts the tokens of `token_stream` and checks which of them are in the vocabulary A of `dictionary`.
+pub(crate) fn coverage_vs_corpus<'a, D, T, V>(
+    dictionary: &D,
+    token_stream: impl Iterator<Item=impl Iterator<Item=&'a str>>
+) -> CoverageReport
+where
+    D: DictionaryWithVocabulary<T, V> + ?Sized,
+    T: Eq + Hash + Borrow<str>,
+    V: BasicVocabulary<T> + SearchableVocabulary<T>
+{
+    let mut frequencies = HashMap::<&'a str, usize>::new();
+    for document in token_stream {
+        for token in document {
+            *frequencies.entry(token).or_insert(0) += 1;
+        }
+    }
+
+    let mut report = CoverageReport::default();
+    let mut uncovered = Vec::new();
+    for (token, frequency) in frequencies {
+        report.type_count += 1;
+        report.token_count += frequency;
+        if dictionary.voc_a().contains(token) {
+            report.covered_type_count += 1;
+            report.covered_token_count += frequency;
+            increment_bucket(&mut report.covered_frequency_histogram, frequency);
+        } else {
+            increment_bucket(&mut report.uncovered_frequency_histogram, frequency);
+            uncovered.push((token, frequency));
+        }
+    }
+    let len = report.covered_frequency_histogram.len().max(report.uncovered_frequency_histogram.len());
+    report.covered_frequency_histogram.resize(len, 0);
+    report.uncovered_frequency_histogram.resize(len, 0);
+    uncovered.sort_by(|(a, freq_a), (b, freq_b)| freq_b.cmp(freq_a).then_with(|| a.cmp(b)));
+    report.uncovered_types = uncovered.into_iter().map(|(token, _)| token.to_string()).collect();
+    report
+}
+
+/// Counts how often two words of the language translated by `D` are translations of the
+/// target words in the same sliding window of `window` consecutive target ids.
+/// Only the pairs `(i, j)` with `i < j` are contained, a window of 0 results in an empty matrix.
+fn cooccurrence_matrix<D, Dict>(dictionary: &Dict, target_count: usize, window: usize) -> HashMap<(usize, usize), u32>
+where
+    D: Translation,
+    Dict: BasicDictionary + ?Sized
+{
+    let mut matrix = HashMap::new();
+    if window == 0 || target_count == 0 {
+        return matrix
+    }
+    let window_count = target_count.saturating_sub(window) + 1;
+    for start in 0..window_count {
+        let words = (start..(start + window).min(target_count))
+            .filter_map(|target| dictionary.translate_id_to_ids::<D>(target))
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        for (pos, i) in words.iter().enumerate() {
+            for j in words.iter().skip(pos + 1) {
+                *matrix.entry((*i, *j)).or_insert(0) += 1;
+            }
+        }
+    }
+    matrix
+}
+
+/// Builds the co-occurrence matrix of vocabulary A, where `(i, j)` counts how often the words
+/// `i` and `j` are translations of the words in a sliding window of `window` words in B.
+/// Only the pairs with `i < j` are contained.
+pub fn cooccurrence_matrix_from_dictionary<T, V, D>(dictionary: &D, window: usize) -> HashMap<(usize, usize), u32>
+where
+    D: DictionaryWithVocabulary<T, V> + ?Sized,
+    V: BasicVocabulary<T>
+{
+    cooccurrence_matrix::<BToA, _>(dictionary, dictionary.voc_b().len(), window)
+}
+
+/// The same as [cooccurrence_matrix_from_dictionary] for vocabulary B with a sliding window over A.
+pub fn cooccurrence_matrix_b_from_dictionary<T, V, D>(dictionary: &D, window: usize) -> HashMap<(usize, usize), u32>
+where
+    D: DictionaryWithVocabulary<T, V> + ?Sized,
+    V: BasicVocabulary<T>
+{
+    cooccurrence_matrix::<AToB, _>(dictionary, dictionary.voc_a().len(), window)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::topicmodel::dictionary::DictionaryWithVocabulary;
+    use crate::topicmodel::dictionary::direction::{AToB, BToA};
+    use crate::topicmodel::dictionary::BasicDictionaryWithVocabulary;
+    use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary, Vocabulary, VocabularyMut};
+    use crate::translate::test::create_test_data;
+
+    #[test]
+    fn plane_roundtrips_through_flugzeug(){
+        let (voc_a, _, dict) = create_test_data();
+        let report = dict.roundtrip_coverage(&voc_a);
+        assert_eq!(voc_a.len(), report.word_count);
+        assert_eq!(voc_a.len(), report.translatable_count);
+        assert_eq!(voc_a.len(), report.recovered_count);
+        assert_eq!(1.0, report.coverage());
+        assert!(report.average_translations_b >= 1.0);
+        assert!(report.average_retranslations_a >= 1.0);
+
+        let plane = dict.translate_value_to_values::<AToB, _>("plane").unwrap();
+        assert!(plane.iter().any(|value| value.as_str() == "Flugzeug"));
+        let flugzeug = dict.translate_value_to_values::<BToA, _>("Flugzeug").unwrap();
+        assert!(flugzeug.iter().any(|value| value.as_str() == "plane"));
+
+        let mut unknown = Vocabulary::<String>::default();
+        unknown.add("plane");
+        unknown.add("helicopter");
+        let report = dict.roundtrip_coverage(&unknown);
+        assert_eq!(2, report.word_count);
+        assert_eq!(1, report.translatable_count);
+        assert_eq!(1, report.recovered_count);
+        assert_eq!(0.5, report.coverage());
+    }
+
+    #[test]
+    fn can_measure_coverage_vs_corpus(){
+        let (_, _, dict) = create_test_data();
+        let corpus = [
+            "the plane has a wing",
+            "the wing of the plane",
+            "a deck and a foil",
+        ];
+        let report = dict.coverage_vs_corpus(corpus.iter().map(|sentence| sentence.split_whitespace()));
+        assert_eq!(9, report.type_count);
+        assert_eq!(4, report.covered_type_count);
+        assert_eq!(15, report.token_count);
+        assert_eq!(6, report.covered_token_count);
+        assert_eq!(4.0 / 9.0, report.type_coverage());
+        assert_eq!(6.0 / 15.0, report.token_coverage());
+        assert_eq!(vec![2, 2], report.covered_frequency_histogram);
+        assert_eq!(vec![3, 2], report.uncovered_frequency_histogram);
+        assert_eq!(vec!["a", "the", "and", "has", "of"], report.uncovered_types());
+    }
+
+    #[test]
+    fn can_count_cooccurrences_of_the_aviation_dictionary(){
+        let (_, _, dict) = create_test_data();
+        let plane = dict.voc_a().get_id("plane").unwrap();
+        let aircraft = dict.voc_a().get_id("aircraft").unwrap();
+        let airplane = dict.voc_a().get_id("airplane").unwrap();
+        let flyer = dict.voc_a().get_id("flyer").unwrap();
+        let airman = dict.voc_a().get_id("airman").unwrap();
+        let airfoil = dict.voc_a().get_id("airfoil").unwrap();
+        let wing = dict.voc_a().get_id("wing").unwrap();
+
+        let single = dict.vocabulary_a_cooccurrence(1);
+        // Flugzeug with 3, Flieger with 5 and Tragfläche with 7 words in A.
+        assert_eq!(3 + 10 + 21, single.values().sum::<u32>());
+        assert_eq!(Some(&2), single.get(&(plane, aircraft)));
+        assert_eq!(Some(&2), single.get(&(aircraft, airplane)));
+        assert_eq!(Some(&1), single.get(&(flyer, airman)));
+        assert_eq!(Some(&1), single.get(&(airfoil, wing)));
+        assert_eq!(None, single.get(&(aircraft, plane)));
+        assert_eq!(None, single.get(&(aircraft, wing)));
+        assert!(single.keys().all(|(i, j)| i < j));
+
+        let double = dict.vocabulary_a_cooccurrence(2);
+        assert_eq!(Some(&3), double.get(&(plane, aircraft)));
+        assert_eq!(Some(&2), double.get(&(flyer, airman)));
+        assert_eq!(Some(&2), double.get(&(airfoil, wing)));
+        assert_eq!(Some(&1), double.get(&(aircraft, wing)));
+
+        assert!(dict.vocabulary_a_cooccurrence(0).is_empty());
+
+        let flugzeug = dict.voc_b().get_id("Flugzeug").unwrap();
+        let flieger = dict.voc_b().get_id("Flieger").unwrap();
+        let b = dict.vocabulary_b_cooccurrence(1);
+        assert_eq!(Some(&3), b.get(&(flugzeug.min(flieger), flugzeug.max(flieger))));
+    }
+}
@@ -0,0 +1,106 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::cmp::Reverse;
+use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithVocabulary, Dictionary, DictionaryWithVocabulary};
+use crate::topicmodel::vocabulary::BasicVocabulary;
+
+/// A read only view on a [Dictionary] where the translations of every word are sorted.
+/// The dictionary itself is not modified.
+#[derive(Debug)]
+pub struct SortedTranslationView<'a, T, V> {
+    dict: &'a Dictionary<T, V>,
+    order_a_to_b: Vec<Vec<usize>>,
+    order_b_to_a: Vec<Vec<usize>>,
+    switched: bool
+}
+
+impl<'a, T, V> Clone for SortedTranslationView<'a, T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            dict: self.dict,
+            order_a_to_b: self.order_a_to_b.clone(),
+            order_b_to_a: self.order_b_to_a.clone(),
+            switched: self.switched
+        }
+    }
+}
+
+impl<'a, T, V> SortedTranslationView<'a, T, V> {
+    /// The viewed dictionary.
+    pub fn dictionary(&self) -> &'a Dictionary<T, V> {
+        self.dict
+    }
+}
+
+impl<T, V> Dictionary<T, V> {
+    /// Creates a view where the translations of every word are sorted descending by the frequency
+    /// of the translated words, ties keep the order of the dictionary.
+    /// `freq_a` and `freq_b` are indexed by the word ids, missing frequencies are treated as 0.
+    pub fn sorted_view_by_frequency(&self, freq_a: &[u32], freq_b: &[u32]) -> SortedTranslationView<T, V> {
+        fn sort_by_frequency(mapping: &[Vec<usize>], frequencies: &[u32]) -> Vec<Vec<usize>> {
+            mapping.iter().map(|translations| {
+                let mut translations = translations.clone();
+                translations.sort_by_key(|id| Reverse(frequencies.get(*id).copied().unwrap_or(0)));
+                translations
+            }).collect()
+        }
+
+        SortedTranslationView {
+            dict: self,
+            order_a_to_b: sort_by_frequency(&self.map_a_to_b, freq_b),
+            order_b_to_a: sort_by_frequency(&self.map_b_to_a, freq_a),
+            switched: false
+        }
+    }
+}
+
+impl<'a, T, V> BasicDictionary for SortedTranslationView<'a, T, V> {
+    fn map_a_to_b(&self) -> &Vec<Vec<usize>> {
+        &self.order_a_to_b
+    }
+
+    fn map_b_to_a(&self) -> &Vec<Vec<usize>> {
+        &self.order_b_to_a
+    }
+
+    fn switch_languages(self) -> Self where Self: Sized {
+        Self {
+            dict: self.dict,
+            order_a_to_b: self.order_b_to_a,
+            order_b_to_a: self.order_a_to_b,
+            switched: !self.switched
+        }
+    }
+}
+
+impl<'a, T, V> BasicDictionaryWithVocabulary<V> for SortedTranslationView<'a, T, V> {
+    fn voc_a(&self) -> &V {
+        if self.switched {
+            &self.dict.voc_b
+        } else {
+            &self.dict.voc_a
+        }
+    }
+
+    fn voc_b(&self) -> &V {
+        if self.switched {
+            &self.dict.voc_a
+        } else {
+            &self.dict.voc_b
+        }
+    }
+}
+
+impl<'a, T, V> DictionaryWithVocabulary<T, V> for SortedTranslationView<'a, T, V> where V: BasicVocabulary<T> {}
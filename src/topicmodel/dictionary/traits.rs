@@ -1,9 +1,11 @@
 use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use crate::topicmodel::dictionary::DictionaryWithMetaIter;
-use crate::topicmodel::dictionary::direction::{Direction, DirectionTuple, Language, Translation, A, B};
+use crate::topicmodel::dictionary::{DictionaryEntry, DictionaryWithMetaIter};
+use crate::topicmodel::dictionary::direction::{AToB, BToA, Direction, DirectionKind, DirectionTuple, Invariant, Language, LanguageKind, Translation, A, B};
 use crate::topicmodel::dictionary::iterators::{DictIter, DictIterImpl, DictLangIter};
 use crate::topicmodel::dictionary::metadata::MetadataContainer;
+use crate::topicmodel::dictionary::quality::{cooccurrence_matrix_b_from_dictionary, cooccurrence_matrix_from_dictionary, coverage_vs_corpus, roundtrip_coverage, CoverageReport, RoundtripReport};
 use crate::topicmodel::language_hint::LanguageHint;
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary, VocabularyMut};
@@ -23,6 +25,12 @@ pub trait BasicDictionary: Send + Sync {
         }
     }
 
+    /// Translates all `word_ids` from A to B, equivalent to calling [BasicDictionary::translate_id_to_ids] for each id.
+    fn translate_words_batch_a_to_b<'a>(&'a self, word_ids: &[usize]) -> Vec<Option<&'a Vec<usize>>> {
+        let mapping = self.map_a_to_b();
+        word_ids.iter().map(|word_id| mapping.get(*word_id)).collect()
+    }
+
     /// Switches language a and b
     fn switch_languages(self) -> Self where Self: Sized;
 
@@ -31,6 +39,110 @@ pub trait BasicDictionary: Send + Sync {
     fn iter(&self) -> DictIter {
         DictIterImpl::new(self)
     }
+
+    /// Returns all `(word_id, translation_count)` pairs of the words with at least `min_translations`
+    /// translations in the direction `D`, sorted descending by the translation count.
+    fn find_polysemous_words<D: Translation>(&self, min_translations: usize) -> Vec<(usize, usize)> {
+        let mapping = if D::DIRECTION.is_a_to_b() {
+            self.map_a_to_b()
+        } else {
+            self.map_b_to_a()
+        };
+        let mut result = mapping
+            .iter()
+            .enumerate()
+            .filter_map(|(word_id, translations)| {
+                (translations.len() >= min_translations).then_some((word_id, translations.len()))
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|(id_a, count_a), (id_b, count_b)| count_b.cmp(count_a).then(id_a.cmp(id_b)));
+        result
+    }
+
+    /// Returns the `n` words with the most translations in the direction `D`.
+    fn most_polysemous<D: Translation>(&self, n: usize) -> Vec<(usize, usize)> {
+        let mut result = self.find_polysemous_words::<D>(1);
+        result.truncate(n);
+        result
+    }
+
+    /// Returns all `(word_id, translation_count)` pairs of the words in language A with at least `min_translations` translations.
+    fn find_polysemous_words_a(&self, min_translations: usize) -> Vec<(usize, usize)> {
+        self.find_polysemous_words::<AToB>(min_translations)
+    }
+
+    /// Returns the `n` words in language A with the most translations.
+    fn most_polysemous_a(&self, n: usize) -> Vec<(usize, usize)> {
+        self.most_polysemous::<AToB>(n)
+    }
+
+    /// The jaccard similarity of the translations of two words in language A.
+    /// Returns 0.0 if one of the words is unknown or both have no translations.
+    fn jaccard_similarity_a(&self, word_id1: usize, word_id2: usize) -> f64 {
+        let (Some(translations1), Some(translations2)) = (
+            self.map_a_to_b().get(word_id1),
+            self.map_a_to_b().get(word_id2)
+        ) else {
+            return 0.0
+        };
+        let translations1 = translations1.iter().collect::<HashSet<_>>();
+        let translations2 = translations2.iter().collect::<HashSet<_>>();
+        let union = translations1.union(&translations2).count();
+        if union == 0 {
+            0.0
+        } else {
+            translations1.intersection(&translations2).count() as f64 / union as f64
+        }
+    }
+
+    /// Returns all `(word_id, similarity)` pairs of the words in language A sharing at least one
+    /// translation with `word_id` and a [jaccard similarity](BasicDictionary::jaccard_similarity_a)
+    /// of at least `threshold`, sorted descending by the similarity.
+    fn find_synonym_candidates_a(&self, word_id: usize, threshold: f64) -> Vec<(usize, f64)> {
+        let Some(translations) = self.map_a_to_b().get(word_id) else {
+            return Vec::new()
+        };
+        let candidates = translations
+            .iter()
+            .filter_map(|id_b| self.map_b_to_a().get(*id_b))
+            .flatten()
+            .copied()
+            .filter(|candidate| *candidate != word_id)
+            .collect::<HashSet<_>>();
+        let mut result = candidates
+            .into_iter()
+            .map(|candidate| (candidate, self.jaccard_similarity_a(word_id, candidate)))
+            .filter(|(_, similarity)| *similarity >= threshold)
+            .collect::<Vec<_>>();
+        result.sort_by(|(id_a, sim_a), (id_b, sim_b)| sim_b.total_cmp(sim_a).then(id_a.cmp(id_b)));
+        result
+    }
+
+    /// Iterates over all `(id_a, id_b, direction)` translation pairs, each pair is emitted exactly once.
+    /// A pair is [DirectionKind::Invariant] if it is contained in both mappings, otherwise
+    /// it has the direction of the mapping containing it.
+    fn iter_distinct_translation_pairs(&self) -> impl Iterator<Item=(usize, usize, DirectionKind)> + '_ {
+        let a_to_b = self.map_a_to_b();
+        let b_to_a = self.map_b_to_a();
+        let contains = |mapping: &Vec<Vec<usize>>, from: usize, to: usize| {
+            mapping.get(from).is_some_and(|value| value.contains(&to))
+        };
+        let pairs_a_to_b = a_to_b.iter().enumerate().flat_map(move |(a, translations)| {
+            translations.iter().map(move |&b| {
+                if contains(b_to_a, b, a) {
+                    (a, b, DirectionKind::Invariant)
+                } else {
+                    (a, b, DirectionKind::AToB)
+                }
+            })
+        });
+        let pairs_b_to_a = b_to_a.iter().enumerate().flat_map(move |(b, translations)| {
+            translations.iter().filter_map(move |&a| {
+                (!contains(a_to_b, a, b)).then_some((a, b, DirectionKind::BToA))
+            })
+        });
+        pairs_a_to_b.chain(pairs_b_to_a)
+    }
 }
 
 /// A basic dictionary with a vocabulary
@@ -66,6 +178,56 @@ pub trait DictionaryWithVocabulary<T, V>: BasicDictionaryWithVocabulary<V> where
         }
     }
 
+    /// Finds the connected components of the dictionary, treated as a bipartite graph with the
+    /// words of both vocabularies as nodes and the translations as edges. Words without any
+    /// translation form a singleton component. The components are ordered by their first word,
+    /// the words of a component are ordered by the language and the word id.
+    fn connected_components(&self) -> Vec<Vec<(usize, LanguageKind)>> {
+        let len_a = self.voc_a().len().max(self.map_a_to_b().len());
+        let len_b = self.voc_b().len().max(self.map_b_to_a().len());
+        // The nodes of language b are offset by len_a.
+        let mut parents = (0..len_a + len_b).collect::<Vec<_>>();
+        fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+            while parents[node] != node {
+                parents[node] = parents[parents[node]];
+                node = parents[node];
+            }
+            node
+        }
+        for (a, b, _) in self.iter_distinct_translation_pairs() {
+            if a >= len_a || b >= len_b {
+                continue
+            }
+            let root_a = find_root(&mut parents, a);
+            let root_b = find_root(&mut parents, len_a + b);
+            if root_a != root_b {
+                let (root, child) = if root_a < root_b { (root_a, root_b) } else { (root_b, root_a) };
+                parents[child] = root;
+            }
+        }
+        let mut component_of_root = vec![usize::MAX; len_a + len_b];
+        let mut components: Vec<Vec<(usize, LanguageKind)>> = Vec::new();
+        for node in 0..len_a + len_b {
+            let root = find_root(&mut parents, node);
+            if component_of_root[root] == usize::MAX {
+                component_of_root[root] = components.len();
+                components.push(Vec::new());
+            }
+            let word = if node < len_a {
+                (node, LanguageKind::A)
+            } else {
+                (node - len_a, LanguageKind::B)
+            };
+            components[component_of_root[root]].push(word);
+        }
+        components
+    }
+
+    /// The number of words in the largest [connected component](DictionaryWithVocabulary::connected_components).
+    fn largest_component(&self) -> usize {
+        self.connected_components().iter().map(|component| component.len()).max().unwrap_or(0)
+    }
+
     /// Convert an ID to a word
     fn id_to_word<'a, D: Translation>(&'a self, id: usize) -> Option<&'a HashRef<T>> where V: 'a {
         if D::DIRECTION.is_a_to_b() {
@@ -170,6 +332,53 @@ pub trait DictionaryWithVocabulary<T, V>: BasicDictionaryWithVocabulary<V> where
     {
         Some(self.ids_to_values::<D>(self.translate_value_to_ids::<D, Q>(word)?))
     }
+
+    /// Translates all `words` from A to B, equivalent to calling [DictionaryWithVocabulary::translate_value_to_values] for each word.
+    fn translate_words_a_to_b<'a>(&'a self, words: &[&str]) -> Vec<Option<Vec<&'a T>>>
+    where
+        T: Borrow<str> + Eq + Hash,
+        V: 'a + SearchableVocabulary<T>
+    {
+        let voc_b = self.voc_b();
+        words.iter().map(|word| {
+            let word_id = self.voc_a().get_id(*word)?;
+            let translations = self.translate_id_to_ids::<AToB>(word_id)?;
+            Some(
+                translations
+                    .iter()
+                    .map(|id| voc_b.get_value(*id).expect("The translation has to exist!").as_ref())
+                    .collect()
+            )
+        }).collect()
+    }
+
+    /// Translates every word of `voc_a` to B and back to A and reports how often the original word is recovered.
+    fn roundtrip_coverage(&self, voc_a: &impl BasicVocabulary<T>) -> RoundtripReport
+    where
+        T: Eq + Hash,
+        V: SearchableVocabulary<T>
+    {
+        roundtrip_coverage(self, voc_a)
+    }
+
+    /// Counts the tokens of the tokenized documents in `token_stream` and reports how many of them are in vocabulary A.
+    fn coverage_vs_corpus<'a>(&self, token_stream: impl Iterator<Item=impl Iterator<Item=&'a str>>) -> CoverageReport
+    where
+        T: Eq + Hash + Borrow<str>,
+        V: SearchableVocabulary<T>
+    {
+        coverage_vs_corpus(self, token_stream)
+    }
+
+    /// The co-occurrence matrix of vocabulary A with a sliding `window` over B, see [cooccurrence_matrix_from_dictionary].
+    fn vocabulary_a_cooccurrence(&self, window: usize) -> HashMap<(usize, usize), u32> {
+        cooccurrence_matrix_from_dictionary::<T, V, Self>(self, window)
+    }
+
+    /// The co-occurrence matrix of vocabulary B with a sliding `window` over A, see [cooccurrence_matrix_b_from_dictionary].
+    fn vocabulary_b_cooccurrence(&self, window: usize) -> HashMap<(usize, usize), u32> {
+        cooccurrence_matrix_b_from_dictionary::<T, V, Self>(self, window)
+    }
 }
 
 pub trait DictionaryMut<T, V>: DictionaryWithVocabulary<T, V> where T: Eq + Hash, V: VocabularyMut<T> {
@@ -184,6 +393,15 @@ pub trait DictionaryMut<T, V>: DictionaryWithVocabulary<T, V> where T: Eq + Hash
     fn insert<D: Direction>(&mut self, word_a: impl Into<T>, word_b: impl Into<T>) -> DirectionTuple<usize, usize> {
         self.insert_value::<D>(word_a.into(), word_b.into())
     }
+
+    /// Inserts the `entry` with its direction.
+    fn insert_entry(&mut self, entry: DictionaryEntry<T>) -> DirectionTuple<usize, usize> {
+        match entry.direction {
+            DirectionKind::AToB => self.insert_value::<AToB>(entry.word_a, entry.word_b),
+            DirectionKind::BToA => self.insert_value::<BToA>(entry.word_a, entry.word_b),
+            DirectionKind::Invariant => self.insert_value::<Invariant>(entry.word_a, entry.word_b),
+        }
+    }
 }
 
 pub trait DictionaryFilterable<T, V>: DictionaryMut<T, V> where T: Eq + Hash, V: VocabularyMut<T> + Default {
@@ -199,6 +417,11 @@ pub trait FromVoc<T, V>: DictionaryWithVocabulary<T, V> where T: Eq + Hash, V: B
 }
 
 
+/// Allows to create a dictionary from a vocabulary while keeping the interned metadata of a source dictionary.
+pub trait FromVocWithMeta<T, V>: FromVoc<T, V> + BasicDictionaryWithMeta where T: Eq + Hash, V: BasicVocabulary<T> {
+    fn from_voc_lang_with_meta<L: Language>(voc: V, other_lang: Option<LanguageHint>, metadata: &MetadataContainer) -> Self;
+}
+
 pub trait BasicDictionaryWithMeta: BasicDictionary {
     fn metadata(&self) -> &MetadataContainer;
     fn metadata_mut(&mut self) -> &mut MetadataContainer;
@@ -64,4 +64,73 @@ pub enum ReadError<E: Debug> {
     ReaderError(#[from] TopicModelIOError),
     #[error("The model at {0} is not finished!")]
     NotFinishedError(PathBuf)
-}
\ No newline at end of file
+}
+
+/// The errors while reading the top words of a topic model from a csv.
+/// Rows and columns are 1-based, the header is row 1.
+#[derive(Debug, Error)]
+pub enum CsvLoadError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("Expected the header \"topic_id,word,probability\" but got \"{0}\"!")]
+    IllegalHeader(String),
+    #[error("The csv is empty!")]
+    Empty,
+    #[error("Row {row} is missing column {column}!")]
+    MissingColumn {
+        row: usize,
+        column: usize
+    },
+    #[error("Row {row} has an unexpected value in column {column}!")]
+    TooManyColumns {
+        row: usize,
+        column: usize
+    },
+    #[error("The quoted value at {row}:{column} is not terminated!")]
+    UnterminatedQuote {
+        row: usize,
+        column: usize
+    },
+    #[error("Failed at {row}:{column} with {err:?}")]
+    ParseFloat {
+        row: usize,
+        column: usize,
+        #[source]
+        err: std::num::ParseFloatError
+    },
+    #[error("Failed at {row}:{column} with {err:?}")]
+    ParseInt {
+        row: usize,
+        column: usize,
+        #[source]
+        err: std::num::ParseIntError
+    },
+    #[error("The topic id {topic_id} in row {row} is not in 0..{topic_count}, every topic needs at least one row!")]
+    TopicIdOutOfRange {
+        row: usize,
+        topic_id: usize,
+        topic_count: usize
+    },
+}
+
+/// The errors when creating an ensemble of topic models.
+#[derive(Debug, Error, PartialEq)]
+pub enum EnsembleError {
+    #[error("An ensemble needs at least one model!")]
+    Empty,
+    #[error("Got {models} models but {weights} weights!")]
+    WeightCountMismatch {
+        models: usize,
+        weights: usize
+    },
+    #[error("The weight {0} is not a finite non-negative number!")]
+    InvalidWeight(f64),
+    #[error("The sum of the weights has to be positive!")]
+    ZeroWeights,
+    #[error("Expected {expected} topics but model {model} has {actual} topics!")]
+    TopicCountMismatch {
+        model: usize,
+        expected: usize,
+        actual: usize
+    },
+}
@@ -41,4 +41,76 @@ pub(crate) fn transpose<'a>(v: &'a Vec<Vec<f64>>) -> Map<Range<usize>, impl FnMu
 {
     assert!(!v.is_empty());
     (0..v[0].len()).map(|i| v.iter().map(|inner| inner[i]).collect::<Vec<_>>())
-}
\ No newline at end of file
+}
+
+/// The Kullback-Leibler divergence KL(P||Q) in bits. Returns infinity if `q` is zero where `p` is not.
+pub(crate) fn kl_divergence(p: &[f64], q: &[f64]) -> f64 {
+    p.iter().zip_eq(q.iter()).map(|(p, q)| {
+        if *p <= 0.0 {
+            0.0
+        } else if *q <= 0.0 {
+            f64::INFINITY
+        } else {
+            p * (p / q).log2()
+        }
+    }).sum()
+}
+
+/// The Shannon entropy of `p` in bits, `p` is normalized before.
+pub(crate) fn entropy(p: &[f64]) -> f64 {
+    let sum = p.iter().sum::<f64>();
+    if sum <= 0.0 {
+        return 0.0
+    }
+    -p.iter().map(|value| value / sum).filter(|value| *value > 0.0).map(|value| value * value.log2()).sum::<f64>()
+}
+
+/// The [entropy] of `p` divided by the maximal entropy `log2(p.len())`, the result lies in `[0, 1]`.
+pub(crate) fn normalized_entropy(p: &[f64]) -> f64 {
+    if p.len() < 2 {
+        return 0.0
+    }
+    entropy(p) / (p.len() as f64).log2()
+}
+
+/// The Jensen-Shannon divergence `0.5 * KL(P||M) + 0.5 * KL(Q||M)` with `M = 0.5 * (P + Q)` in bits.
+/// `p` and `q` are normalized before, the result lies in `[0, 1]`.
+pub(crate) fn jensen_shannon_divergence(p: &[f64], q: &[f64]) -> f64 {
+    fn normalize(values: &[f64]) -> Vec<f64> {
+        let sum = values.iter().sum::<f64>();
+        if sum > 0.0 {
+            values.iter().map(|value| value / sum).collect()
+        } else {
+            values.to_vec()
+        }
+    }
+    let p = normalize(p);
+    let q = normalize(q);
+    let m = p.iter().zip_eq(q.iter()).map(|(p, q)| 0.5 * (p + q)).collect_vec();
+    0.5 * kl_divergence(&p, &m) + 0.5 * kl_divergence(&q, &m)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::topicmodel::math::{entropy, jensen_shannon_divergence, kl_divergence, normalized_entropy};
+
+    #[test]
+    fn kl_and_jsd_behave(){
+        let p = [0.5, 0.5, 0.0];
+        let q = [0.0, 0.5, 0.5];
+        assert_eq!(0.0, kl_divergence(&p, &p));
+        assert_eq!(f64::INFINITY, kl_divergence(&p, &q));
+        assert_eq!(0.5, jensen_shannon_divergence(&p, &q));
+        assert_eq!(1.0, jensen_shannon_divergence(&[1.0, 0.0], &[0.0, 1.0]));
+        assert_eq!(0.0, jensen_shannon_divergence(&[2.0, 2.0], &[0.5, 0.5]));
+    }
+
+    #[test]
+    fn entropy_behaves(){
+        assert_eq!(2.0, entropy(&[0.25, 0.25, 0.25, 0.25]));
+        assert_eq!(1.0, entropy(&[3.0, 3.0, 0.0]));
+        assert_eq!(0.0, entropy(&[1.0, 0.0]));
+        assert_eq!(1.0, normalized_entropy(&[0.25, 0.25, 0.25, 0.25]));
+        assert_eq!(0.5, normalized_entropy(&[0.5, 0.5, 0.0, 0.0]));
+    }
+}
@@ -19,6 +19,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::sync::Arc;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// A ref that supplies the Hash and Eq method of the underlying struct.
 /// It is threadsafe and allows a simple cloning as well as ordering
@@ -40,6 +41,14 @@ impl<T> HashRef<T> {
     }
 }
 
+impl<T: ?Sized> HashRef<T> {
+    /// Returns true if both refs point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+}
+
 impl<T: Hash> Hash for HashRef<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.deref().hash(state)
@@ -121,3 +130,15 @@ impl<T> From<T> for HashRef<T>  {
         Self::new(value)
     }
 }
+
+impl<T: ?Sized + Serialize> Serialize for HashRef<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for HashRef<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
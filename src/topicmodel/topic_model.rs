@@ -15,6 +15,9 @@
 #![allow(dead_code)]
 
 pub mod meta;
+mod subset;
+
+pub use subset::TopicSubset;
 
 use std::borrow::{Borrow};
 use std::cmp::{min, Ordering, Reverse};
@@ -37,18 +40,19 @@ use approx::relative_eq;
 use flate2::Compression;
 use itertools::{Itertools, multiunzip, multizip};
 use pyo3::{FromPyObject};
-use rand::thread_rng;
-use rand_distr::Distribution;
+use rand::rngs::StdRng;
+use rand::{thread_rng, SeedableRng};
+use rand_distr::{Bernoulli, Distribution};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::toolkit::normal_number::IsNormalNumber;
 
-use crate::topicmodel::enums::{ReadError, TopicModelVersion, WriteError};
+use crate::topicmodel::enums::{CsvLoadError, EnsembleError, ReadError, TopicModelVersion, WriteError};
 use crate::topicmodel::enums::ReadError::NotFinishedError;
 use crate::topicmodel::traits::{ToParseableString};
 use crate::topicmodel::io::{TopicModelFSRead, TopicModelFSWrite};
 use crate::topicmodel::io::TopicModelIOError::PathNotFound;
-use crate::topicmodel::math::{dirichlet_expectation_1d, dirichlet_expectation_2d, dot, transpose};
+use crate::topicmodel::math::{dirichlet_expectation_1d, dirichlet_expectation_2d, dot, entropy, jensen_shannon_divergence, normalized_entropy, transpose};
 use crate::topicmodel::reference::HashRef;
 use crate::topicmodel::topic_model::meta::*;
 use crate::topicmodel::vocabulary::{LoadableVocabulary, MappableVocabulary, StoreableVocabulary, BasicVocabulary, Vocabulary, VocabularyMut};
@@ -138,8 +142,131 @@ pub trait BasicTopicModel: Send + Sync {
     /// Get the `n` best [WordMeta] in `topic_id` by their position.
     fn get_n_best_for_topic(&self, topic_id: TopicId, n: usize) -> Option<&[Arc<WordMeta>]>;
 
+    /// Returns a view containing only the topics with `topic_ids` in the given order.
+    /// Returns None if any of the `topic_ids` is not in self.
+    fn select_topics(&self, topic_ids: &[TopicId]) -> Option<TopicSubset<Self>> where Self: Sized {
+        TopicSubset::new(self, topic_ids)
+    }
+
     /// Get the `n` best [WordMeta] for all topics by their position.
     fn get_n_best_for_topics(&self, n: usize) -> Option<TopicTo<&[Arc<WordMeta>]>>;
+
+    /// The `(word_id, frequency)` of all words sorted descending by the [BasicTopicModel::used_vocab_frequency].
+    /// Words with the same frequency are sorted by their id.
+    fn frequency_ranking(&self) -> Vec<(WordId, WordFrequency)> {
+        self.used_vocab_frequency()
+            .iter()
+            .copied()
+            .enumerate()
+            .sorted_by(|(id_a, freq_a), (id_b, freq_b)| freq_b.cmp(freq_a).then(id_a.cmp(id_b)))
+            .collect()
+    }
+
+    /// The 1-based rank of `word_id` in the [frequency ranking](BasicTopicModel::frequency_ranking),
+    /// 1 is the most frequent word.
+    fn rank_of(&self, word_id: WordId) -> Option<usize> {
+        let frequencies = self.used_vocab_frequency();
+        let frequency = *frequencies.get(word_id)?;
+        let before = frequencies
+            .iter()
+            .enumerate()
+            .filter(|(id, other)| **other > frequency || (**other == frequency && *id < word_id))
+            .count();
+        Some(before + 1)
+    }
+
+    /// Checks if the frequencies roughly follow Zipf's law `frequency(rank) ≈ C / rank`.
+    /// C is estimated as the mean of `frequency(rank) * rank` and the relative deviation
+    /// of each nonzero frequency from `C / rank` has to be at most `tolerance`.
+    fn is_approximately_zipf(&self, tolerance: f64) -> bool {
+        let ranking = self.frequency_ranking()
+            .into_iter()
+            .filter(|(_, frequency)| *frequency > 0)
+            .map(|(_, frequency)| frequency as f64)
+            .collect_vec();
+        if ranking.len() < 2 {
+            return false
+        }
+        let c = ranking.iter().enumerate().map(|(idx, frequency)| frequency * (idx + 1) as f64).sum::<f64>() / ranking.len() as f64;
+        ranking.iter().enumerate().all(|(idx, frequency)| {
+            let expected = c / (idx + 1) as f64;
+            ((frequency - expected) / expected).abs() <= tolerance
+        })
+    }
+
+    /// The share of the most probable word above which a topic is
+    /// [degenerated](BasicTopicModel::degenerate_topics).
+    const SINGLE_WORD_DOMINANCE: f64 = 0.9;
+
+    /// The ids of the topics that are too uniform, the normalized entropy
+    /// (entropy divided by `log2(vocabulary_size)`) is at least `entropy_threshold`,
+    /// or dominated by a single word with a share of at least [BasicTopicModel::SINGLE_WORD_DOMINANCE].
+    fn degenerate_topics(&self, entropy_threshold: f64) -> Vec<TopicId> {
+        self.topics().iter().enumerate().filter_map(|(topic_id, topic)| {
+            let sum = topic.iter().sum::<f64>();
+            let max = topic.iter().copied().fold(0.0, f64::max);
+            let is_degenerate = normalized_entropy(topic) >= entropy_threshold
+                || (sum > 0.0 && max / sum >= Self::SINGLE_WORD_DOMINANCE);
+            is_degenerate.then_some(topic_id)
+        }).collect()
+    }
+
+    /// The global probability of each word, calculated from [BasicTopicModel::used_vocab_frequency].
+    fn global_word_probabilities(&self) -> WordTo<Probability> {
+        let frequencies = self.used_vocab_frequency();
+        let sum = frequencies.iter().sum::<WordFrequency>() as f64;
+        frequencies.iter().map(|frequency| *frequency as f64 / sum).collect()
+    }
+
+    /// The relevance of each word in each topic as defined by LDAvis:
+    /// `lambda * log P(w|t) + (1 - lambda) * log (P(w|t) / P(w))`
+    ///
+    /// `global_word_probs` contains P(w) for each word, returns None if the length does not match
+    /// the size of the vocabulary.
+    fn word_relevance_scores(&self, lambda: f64, global_word_probs: &[Probability]) -> Option<TopicTo<WordTo<f64>>> {
+        if self.vocabulary_size() != global_word_probs.len() {
+            return None
+        }
+        Some(self.topics().iter().map(|topic| {
+            topic.iter().zip_eq(global_word_probs.iter()).map(|(probability, global_probability)| {
+                lambda * probability.ln() + (1.0 - lambda) * (probability / global_probability).ln()
+            }).collect()
+        }).collect())
+    }
+
+    /// Returns the word ids of the `n` most relevant words for each topic, sorted descending
+    /// by the [relevance](BasicTopicModel::word_relevance_scores).
+    fn top_n_by_relevance(&self, lambda: f64, global_word_probs: &[Probability], n: usize) -> Option<TopicTo<Vec<WordId>>> {
+        Some(self.word_relevance_scores(lambda, global_word_probs)?.into_iter().map(|scores| {
+            scores
+                .into_iter()
+                .enumerate()
+                .sorted_by(|(id_a, score_a), (id_b, score_b)| score_b.total_cmp(score_a).then(id_a.cmp(id_b)))
+                .take(n)
+                .map(|(word_id, _)| word_id)
+                .collect()
+        }).collect())
+    }
+
+    /// The Jensen-Shannon divergence between the topics `topic_i` and `topic_j` in bits.
+    /// The square root of the result is the Jensen-Shannon distance.
+    fn compare_topics(&self, topic_i: TopicId, topic_j: TopicId) -> Option<f64> {
+        Some(jensen_shannon_divergence(self.get_topic(topic_i)?, self.get_topic(topic_j)?))
+    }
+
+    /// The mean [Jensen-Shannon divergence](BasicTopicModel::compare_topics) over all pairs of topics.
+    /// A higher score means more diverse topics.
+    fn diversity_score(&self) -> f64 {
+        let topics = self.topics();
+        let (sum, count) = topics.iter().tuple_combinations().fold((0.0, 0usize), |(sum, count), (p, q)| {
+            (sum + jensen_shannon_divergence(p, q), count + 1)
+        });
+        if count == 0 {
+            0.0
+        } else {
+            sum / count as f64
+        }
+    }
 }
 
 /// A topicmodel with document stats
@@ -155,6 +282,41 @@ pub trait TopicModelWithDocumentStats {
 
     /// Returns the document lengths of the documents
     fn document_lengths(&self) -> &DocumentTo<DocumentLength>;
+
+    /// Approximates the number of documents containing each word.
+    /// A word is contained in a document if its expected count
+    /// `document_length * sum_t P(t|d) * P(w|t)` is greater than 0.5.
+    fn document_frequency_vector(&self) -> WordTo<u32> where Self: BasicTopicModel {
+        let mut document_frequencies = vec![0u32; self.vocabulary_size()];
+        for (distribution, length) in self.doc_topic_distributions().iter().zip_eq(self.document_lengths().iter()) {
+            for (word_id, document_frequency) in document_frequencies.iter_mut().enumerate() {
+                let expected = *length as f64 * distribution
+                    .iter()
+                    .zip_eq(self.topics().iter())
+                    .map(|(topic_probability, topic)| topic_probability * topic[word_id])
+                    .sum::<f64>();
+                if expected > 0.5 {
+                    *document_frequency += 1;
+                }
+            }
+        }
+        document_frequencies
+    }
+
+    /// Weights the topic-word probabilities by `ln(N / df + 1)`, where `N` is the number of documents
+    /// and `df` the [document frequency](TopicModelWithDocumentStats::document_frequency_vector)
+    /// of the word. Words without any document are treated like words with a document frequency of 1.
+    fn tfidf_score_per_topic(&self) -> TopicTo<WordTo<f64>> where Self: BasicTopicModel {
+        let document_count = self.document_count() as f64;
+        let idf = self
+            .document_frequency_vector()
+            .into_iter()
+            .map(|document_frequency| (document_count / document_frequency.max(1) as f64 + 1.0).ln())
+            .collect_vec();
+        self.topics().iter().map(|topic| {
+            topic.iter().zip_eq(idf.iter()).map(|(probability, idf)| probability * idf).collect()
+        }).collect()
+    }
 }
 
 /// A basic topic model with a vocabulary
@@ -511,12 +673,202 @@ impl<T, V> TopicModel<T, V> where
     }
 }
 
+impl<T, V> TopicModel<T, V> where T: Hash + Eq, V: VocabularyMut<T> {
+    /// Calculates the [perplexity](TopicModelInferencer::held_out_perplexity) of `held_out_docs`
+    /// with a symmetric alpha of `1/k`.
+    pub fn held_out_perplexity(&self, held_out_docs: &[(Vec<usize>, Vec<u32>)]) -> f64 {
+        TopicModelInferencer::<T, V, Self>::new(self, SingleOrList::Single(1.0 / self.k() as f64), 0.001)
+            .held_out_perplexity(held_out_docs)
+    }
+}
+
 impl<T, V> TopicModel<T, V> where T: Hash + Eq + Ord, V: Clone + VocabularyMut<T> {
     pub fn normalize(&self) -> Self {
         let mut target = self.clone();
         target.normalize_in_place();
         target
     }
+
+    /// Creates a copy without the topics in `topic_ids`, the remaining topics are renumbered
+    /// contiguously and the document topic distributions are renormalized.
+    pub fn without_topics(&self, topic_ids: &[TopicId]) -> Self {
+        let keep = |topic_id: &usize| !topic_ids.contains(topic_id);
+        let topics = self.topics
+            .iter()
+            .enumerate()
+            .filter(|(topic_id, _)| keep(topic_id))
+            .map(|(_, topic)| topic.clone())
+            .collect_vec();
+        let doc_topic_distributions = self.doc_topic_distributions.iter().map(|probabilities| {
+            let probabilities = probabilities
+                .iter()
+                .enumerate()
+                .filter(|(topic_id, _)| keep(topic_id))
+                .map(|(_, probability)| *probability)
+                .collect_vec();
+            let sum = probabilities.iter().sum::<f64>();
+            if sum > 0.0 {
+                probabilities.into_iter().map(|probability| probability / sum).collect_vec()
+            } else {
+                probabilities
+            }
+        }).collect_vec();
+        Self::new(
+            topics,
+            self.vocabulary.clone(),
+            self.used_vocab_frequency.clone(),
+            doc_topic_distributions,
+            self.document_lengths.clone()
+        )
+    }
+
+    /// Removes the topics with an entropy in bits above `max_entropy` and returns their original ids.
+    /// The remaining topics are renumbered like in [TopicModel::without_topics].
+    pub fn prune_topics_by_entropy(&mut self, max_entropy: f64) -> Vec<TopicId> {
+        let removed = self.topics
+            .iter()
+            .enumerate()
+            .filter(|(_, topic)| entropy(topic) > max_entropy)
+            .map(|(topic_id, _)| topic_id)
+            .collect_vec();
+        if !removed.is_empty() {
+            *self = self.without_topics(&removed);
+        }
+        removed
+    }
+
+    /// Removes every topic with a [Jensen-Shannon divergence](BasicTopicModel::compare_topics)
+    /// below `min_jsd_from_others` to a previous, not removed topic and returns their original ids.
+    /// The remaining topics are renumbered like in [TopicModel::without_topics].
+    pub fn prune_topics_by_diversity(&mut self, min_jsd_from_others: f64) -> Vec<TopicId> {
+        let mut kept: Vec<TopicId> = Vec::new();
+        let mut removed = Vec::new();
+        for (topic_id, topic) in self.topics.iter().enumerate() {
+            if kept.iter().any(|other| jensen_shannon_divergence(&self.topics[*other], topic) < min_jsd_from_others) {
+                removed.push(topic_id);
+            } else {
+                kept.push(topic_id);
+            }
+        }
+        if !removed.is_empty() {
+            *self = self.without_topics(&removed);
+        }
+        removed
+    }
+
+    /// Creates a copy where frequent words are removed randomly, like the subsampling of word2vec.
+    /// A word with the relative frequency `f` is removed with the probability
+    /// `max(0, 1 - sqrt(subsample_factor / f))`. The removed words get a probability of 0.0
+    /// and the topics are renormalized. The same `rng_seed` removes the same words.
+    pub fn resample_vocabulary(&self, subsample_factor: f64, rng_seed: u64) -> Self {
+        let total = self.used_vocab_frequency.iter().sum::<WordFrequency>() as f64;
+        let mut rng = StdRng::seed_from_u64(rng_seed);
+        let removed = self.used_vocab_frequency.iter().map(|frequency| {
+            if *frequency == 0 {
+                return false
+            }
+            let frequency_ratio = *frequency as f64 / total;
+            let probability = (1.0 - (subsample_factor / frequency_ratio).sqrt()).max(0.0);
+            probability > 0.0 && Bernoulli::new(probability.min(1.0)).unwrap().sample(&mut rng)
+        }).collect_vec();
+
+        let topics = self.topics.iter().map(|topic| {
+            let topic = topic
+                .iter()
+                .zip_eq(removed.iter())
+                .map(|(probability, removed)| if *removed { 0.0 } else { *probability })
+                .collect_vec();
+            let sum = topic.iter().sum::<f64>();
+            if sum > 0.0 {
+                topic.into_iter().map(|probability| probability / sum).collect_vec()
+            } else {
+                topic
+            }
+        }).collect_vec();
+
+        Self::new(
+            topics,
+            self.vocabulary.clone(),
+            self.used_vocab_frequency.clone(),
+            self.doc_topic_distributions.clone(),
+            self.document_lengths.clone()
+        )
+    }
+}
+
+impl<T, V> TopicModel<T, V> where T: Hash + Eq + Ord, V: VocabularyMut<T> + Default {
+    /// Combines the `models` to a single model by the weighted average of the word probabilities
+    /// of each topic. The vocabulary is the union of the vocabularies in the order of the models,
+    /// the missing words of a model get the smallest probability of all models. The topics are
+    /// renormalized afterwards.
+    ///
+    /// The language and the document statistics are taken from the first model,
+    /// the word frequency is the maximum over all models.
+    pub fn ensemble(models: &[&Self], weights: &[f64]) -> Result<Self, EnsembleError> {
+        let Some(first) = models.first() else {
+            return Err(EnsembleError::Empty)
+        };
+        if models.len() != weights.len() {
+            return Err(EnsembleError::WeightCountMismatch { models: models.len(), weights: weights.len() })
+        }
+        if let Some(weight) = weights.iter().find(|weight| !weight.is_finite() || **weight < 0.0) {
+            return Err(EnsembleError::InvalidWeight(*weight))
+        }
+        let weight_sum = weights.iter().sum::<f64>();
+        if weight_sum <= 0.0 {
+            return Err(EnsembleError::ZeroWeights)
+        }
+        let k = first.k();
+        if let Some((model, other)) = models.iter().enumerate().find(|(_, model)| model.k() != k) {
+            return Err(EnsembleError::TopicCountMismatch { model, expected: k, actual: other.k() })
+        }
+
+        let mut vocabulary = V::default();
+        vocabulary.set_language(first.vocabulary.language().cloned());
+        let mappings = models.iter().map(|model| {
+            model.vocabulary.iter().map(|word| vocabulary.add_hash_ref(word.clone())).collect_vec()
+        }).collect_vec();
+
+        let epsilon = models
+            .iter()
+            .flat_map(|model| model.topics.iter().flatten())
+            .copied()
+            .fold(f64::MAX, f64::min);
+
+        let mut topics = vec![vec![0.0; vocabulary.len()]; k];
+        for ((model, mapping), weight) in models.iter().zip_eq(mappings.iter()).zip_eq(weights) {
+            let weight = *weight / weight_sum;
+            let mut contained = vec![false; vocabulary.len()];
+            for word_id in mapping.iter() {
+                contained[*word_id] = true;
+            }
+            for (target, topic) in topics.iter_mut().zip_eq(model.topics.iter()) {
+                for (word_id, probability) in mapping.iter().zip_eq(topic.iter()) {
+                    target[*word_id] += weight * probability;
+                }
+                for (value, _) in target.iter_mut().zip_eq(contained.iter()).filter(|(_, contained)| !**contained) {
+                    *value += weight * epsilon;
+                }
+            }
+        }
+
+        let mut used_vocab_frequency = vec![0; vocabulary.len()];
+        for (model, mapping) in models.iter().zip_eq(mappings.iter()) {
+            for (word_id, frequency) in mapping.iter().zip_eq(model.used_vocab_frequency.iter()) {
+                used_vocab_frequency[*word_id] = used_vocab_frequency[*word_id].max(*frequency);
+            }
+        }
+
+        let mut ensemble = Self::new(
+            topics,
+            vocabulary,
+            used_vocab_frequency,
+            first.doc_topic_distributions.clone(),
+            first.document_lengths.clone()
+        );
+        ensemble.normalize_in_place();
+        Ok(ensemble)
+    }
 }
 
 impl<T, V> TopicModel<T, V> {
@@ -748,6 +1100,118 @@ impl<T: Display, V> TopicModel<T, V> where V: BasicVocabulary<T> {
     pub fn show_10(&self) -> io::Result<()>{
         self.show(10)
     }
+
+    /// Writes the `top_n` words of every topic as a html table to `writer`.
+    /// Every column is a topic, the background of a cell is proportional to the probability of the word.
+    /// The css is inlined, therefore the table can be embedded anywhere.
+    pub fn to_html<W: Write>(&self, mut writer: W, top_n: usize) -> io::Result<()> {
+        fn escape_html(value: &str) -> String {
+            let mut escaped = String::with_capacity(value.len());
+            for c in value.chars() {
+                match c {
+                    '&' => escaped.push_str("&amp;"),
+                    '<' => escaped.push_str("&lt;"),
+                    '>' => escaped.push_str("&gt;"),
+                    '"' => escaped.push_str("&quot;"),
+                    '\'' => escaped.push_str("&#39;"),
+                    other => escaped.push(other)
+                }
+            }
+            escaped
+        }
+
+        let best = self.get_n_best_for_topics(top_n).ok_or(io::Error::from(ErrorKind::Other))?;
+        let rows = best.iter().map(|topic| topic.len()).max().unwrap_or(0);
+
+        writer.write_all(b"<table style=\"border-collapse: collapse; font-family: sans-serif; font-size: 0.9em;\">\n")?;
+        writer.write_all(b"  <tr>")?;
+        for topic_id in 0..best.len() {
+            write!(writer, "<th style=\"border: 1px solid #999; padding: 4px 8px;\">Topic({topic_id})</th>")?;
+        }
+        writer.write_all(b"</tr>\n")?;
+        for row in 0..rows {
+            writer.write_all(b"  <tr>")?;
+            for (topic_id, topic_entries) in best.iter().enumerate() {
+                if let Some(it) = topic_entries.get(row) {
+                    let max_value = self.topic_metas[topic_id].stats.max_value;
+                    let alpha = if max_value > 0.0 { it.probability / max_value } else { 0.0 };
+                    write!(
+                        writer,
+                        "<td style=\"border: 1px solid #999; padding: 4px 8px; background-color: rgba(70, 130, 180, {alpha:.3});\" title=\"{}\">{}</td>",
+                        it.probability,
+                        escape_html(&self.vocabulary.get_value(it.word_id).unwrap().to_string())
+                    )?;
+                } else {
+                    writer.write_all(b"<td style=\"border: 1px solid #999; padding: 4px 8px;\"></td>")?;
+                }
+            }
+            writer.write_all(b"</tr>\n")?;
+        }
+        writer.write_all(b"</table>\n")?;
+        writer.flush()
+    }
+
+    /// Writes the html table created by [TopicModel::to_html] to the file at `path`.
+    pub fn to_html_file(&self, path: impl AsRef<Path>, top_n: usize) -> io::Result<()> {
+        self.to_html(BufWriter::new(std::fs::File::create(path)?), top_n)
+    }
+
+    /// Writes the `top_n` words of every topic as csv with the columns `topic_id,word,probability` to `writer`.
+    /// Words containing a comma, a quote or a line break are quoted.
+    pub fn export_top_words_per_topic_csv<W: Write>(&self, mut writer: W, top_n: usize) -> io::Result<()> {
+        writer.write_all(CSV_HEADER.as_bytes())?;
+        writer.write_all(b"\n")?;
+        for (topic_id, topic_entries) in self.get_n_best_for_topics(top_n).ok_or(io::Error::from(ErrorKind::Other))?.iter().enumerate() {
+            for it in topic_entries.iter() {
+                let word = self.vocabulary.get_value(it.word_id).unwrap().to_string();
+                if word.contains([',', '"', '\n', '\r']) {
+                    writeln!(writer, "{topic_id},\"{}\",{}", word.replace('"', "\"\""), it.probability)?;
+                } else {
+                    writeln!(writer, "{topic_id},{word},{}", it.probability)?;
+                }
+            }
+        }
+        writer.flush()
+    }
+}
+
+/// The method used to aggregate the probabilities of a word over all topics.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum WordImportanceAggregation {
+    /// Sums up the probabilities of a word in all topics.
+    #[default]
+    Sum,
+    /// Uses the highest probability of a word in any topic.
+    Max
+}
+
+impl WordImportanceAggregation {
+    fn aggregate(&self, current: Probability, next: Probability) -> Probability {
+        match self {
+            WordImportanceAggregation::Sum => current + next,
+            WordImportanceAggregation::Max => current.max(next)
+        }
+    }
+}
+
+impl<T, V> TopicModel<T, V> where V: BasicVocabulary<T> {
+    /// Returns all words of the vocabulary with their probabilities aggregated over all topics.
+    /// The result is sorted descending by the aggregated value.
+    pub fn global_word_importance(&self, aggregation: WordImportanceAggregation) -> Vec<(&T, f64)> {
+        let mut importance: WordTo<Probability> = vec![0.0; self.vocabulary.len()];
+        for topic in self.topics.iter() {
+            for (word_id, probability) in topic.iter().enumerate() {
+                importance[word_id] = aggregation.aggregate(importance[word_id], *probability);
+            }
+        }
+        let mut result = importance
+            .into_iter()
+            .enumerate()
+            .map(|(word_id, value)| (self.vocabulary.get_value(word_id).unwrap().as_ref(), value))
+            .collect_vec();
+        result.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        result
+    }
 }
 
 impl<T: Display, V> Display for TopicModel<T, V> where V: Display + BasicVocabulary<T> {
@@ -769,6 +1233,124 @@ impl TopicModel<String, Vocabulary<String>> {
     }
 }
 
+const CSV_HEADER: &str = "topic_id,word,probability";
+
+/// Splits a single csv row into its values, `row` is only used for the errors.
+fn split_csv_row(line: &str, row: usize) -> Result<Vec<String>, CsvLoadError> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+    let mut quote_start = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if current.is_empty() => {
+                in_quotes = true;
+                quote_start = values.len() + 1;
+            }
+            ',' if !in_quotes => {
+                values.push(std::mem::take(&mut current));
+            }
+            other => current.push(other)
+        }
+    }
+    if in_quotes {
+        return Err(CsvLoadError::UnterminatedQuote { row, column: quote_start })
+    }
+    values.push(current);
+    Ok(values)
+}
+
+impl<V> TopicModel<String, V> where V: VocabularyMut<String> + From<Vec<String>> {
+    /// Reconstructs a topic model from a csv written by [TopicModel::export_top_words_per_topic_csv].
+    ///
+    /// The vocabulary consists of all words in the csv, ordered by their first occurrence.
+    /// In every topic the probability mass not covered by the listed words is distributed uniformly
+    /// over the missing words, but every missing word gets at most half of the smallest listed probability
+    /// of the topic. Therefore the listed words stay the top words of their topics.
+    /// The reconstructed model has no document statistics and all word frequencies are 0.
+    pub fn import_top_words_from_csv<R: BufRead>(reader: R) -> Result<Self, CsvLoadError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(CsvLoadError::Empty)??;
+        if header.trim_end() != CSV_HEADER {
+            return Err(CsvLoadError::IllegalHeader(header))
+        }
+
+        let mut words: Vec<String> = Vec::new();
+        let mut word_to_id: HashMap<String, WordId> = HashMap::new();
+        let mut entries: Vec<(usize, TopicId, WordId, Probability)> = Vec::new();
+        for (idx, line) in lines.enumerate() {
+            let row = idx + 2;
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue
+            }
+            let mut values = split_csv_row(line, row)?.into_iter();
+            let topic_id = values.next().ok_or(CsvLoadError::MissingColumn { row, column: 1 })?;
+            let topic_id: TopicId = topic_id.trim().parse().map_err(|err| CsvLoadError::ParseInt { row, column: 1, err })?;
+            let word = values.next().ok_or(CsvLoadError::MissingColumn { row, column: 2 })?;
+            let probability = values.next().ok_or(CsvLoadError::MissingColumn { row, column: 3 })?;
+            let probability: Probability = probability.trim().parse().map_err(|err| CsvLoadError::ParseFloat { row, column: 3, err })?;
+            if values.next().is_some() {
+                return Err(CsvLoadError::TooManyColumns { row, column: 4 })
+            }
+            let word_id = match word_to_id.entry(word) {
+                Entry::Occupied(value) => *value.get(),
+                Entry::Vacant(value) => {
+                    let word_id = words.len();
+                    words.push(value.key().clone());
+                    value.insert(word_id);
+                    word_id
+                }
+            };
+            entries.push((row, topic_id, word_id, probability));
+        }
+
+        // Bounds the allocation of the topics by the size of the csv.
+        if let Some((row, topic_id, _, _)) = entries.iter().find(|(_, topic_id, _, _)| *topic_id >= entries.len()) {
+            return Err(CsvLoadError::TopicIdOutOfRange { row: *row, topic_id: *topic_id, topic_count: entries.len() })
+        }
+
+        let k = entries.iter().map(|(_, topic_id, _, _)| topic_id + 1).max().unwrap_or(0);
+        let mut listed: Vec<Vec<Option<Probability>>> = vec![vec![None; words.len()]; k];
+        for (_, topic_id, word_id, probability) in entries {
+            listed[topic_id][word_id] = Some(probability);
+        }
+
+        let topics = listed.into_iter().map(|topic| {
+            let listed_values = topic.iter().flatten().copied().collect_vec();
+            let missing = topic.len() - listed_values.len();
+            let epsilon = if missing == 0 {
+                0.0
+            } else {
+                let remaining = (1.0 - listed_values.iter().sum::<f64>()) / missing as f64;
+                let smallest = listed_values.iter().copied().fold(f64::INFINITY, f64::min);
+                let epsilon = remaining.min(smallest / 2.0);
+                if epsilon.is_normal() && epsilon > 0.0 { epsilon } else { f64::EPSILON }
+            };
+            topic.into_iter().map(|value| value.unwrap_or(epsilon)).collect_vec()
+        }).collect_vec();
+
+        let used_vocab_frequency = vec![0; words.len()];
+        Ok(TopicModel::new(
+            topics,
+            V::from(words),
+            used_vocab_frequency,
+            Vec::new(),
+            Vec::new()
+        ))
+    }
+}
+
 const MODEL_ZIP_PATH: &str = "model.zip";
 const PATH_TO_DOC_LENGTHS: &str = "doc\\doc_lengths.freq";
 const PATH_TO_DOC_TOPIC_DISTS: &str = "doc\\doc_topic_dists.freq";
@@ -1087,6 +1669,43 @@ impl<'a, T, V, Model> TopicModelInferencer<'a, T, V, Model> where
         }
     }
 
+    /// Calculates the perplexity `exp(-1/N * Σ_d Σ_w count(w,d) * ln p(w|d))` of the bag of words
+    /// `(word_ids, counts)` in `held_out_docs`, where `p(w|d) = Σ_t theta_d(t) * phi_t(w)` and
+    /// theta_d is inferred for each document. Unknown word ids are ignored.
+    pub fn held_out_perplexity(&self, held_out_docs: &[(Vec<usize>, Vec<u32>)]) -> f64 {
+        let chunk = held_out_docs.iter().map(|(word_ids, counts)| {
+            assert_eq!(word_ids.len(), counts.len(), "Expected a count for each word id!");
+            word_ids
+                .iter()
+                .zip(counts.iter())
+                .filter(|(word_id, count)| **count > 0 && self.topic_model.contains_word_id(**word_id))
+                .map(|(word_id, count)| (*word_id, *count as usize))
+                .collect_vec()
+        }).filter(|doc| !doc.is_empty()).collect_vec();
+
+        if chunk.is_empty() {
+            return f64::NAN
+        }
+
+        let (gamma, _) = self.inference(chunk.clone(), false, 1000);
+        let topics = self.topic_model.topics();
+        let mut log_likelihood = 0.0;
+        let mut token_count = 0usize;
+        for (doc, gamma_d) in chunk.iter().zip_eq(gamma.iter()) {
+            let norm_value = gamma_d.iter().sum::<f64>();
+            for (word_id, count) in doc.iter() {
+                let probability = gamma_d
+                    .iter()
+                    .zip_eq(topics.iter())
+                    .map(|(gamma, topic)| gamma / norm_value * topic[*word_id])
+                    .sum::<f64>();
+                log_likelihood += *count as f64 * probability.ln();
+                token_count += *count;
+            }
+        }
+        (-log_likelihood / token_count as f64).exp()
+    }
+
     fn inference(&self, chunk: Vec<Vec<(usize, usize)>>, collect_stats: bool, iterations: usize) -> (Vec<Vec<f64>>, Option<Vec<Vec<f64>>>) {
 
         fn calculate_phi_norm(exp_e_log_theta_d: &Vec<f64>, exp_e_log_beta_d: &Vec<Vec<f64>>) -> Vec<f64> {
@@ -1218,10 +1837,10 @@ impl<'a, T, V, Model> TopicModelInferencer<'a, T, V, Model> where
 #[cfg(test)]
 mod test {
     use itertools::{assert_equal, Itertools};
-    use crate::topicmodel::enums::TopicModelVersion;
-    use crate::topicmodel::topic_model::{TopicModel, TopicModelInferencer, TopicModelWithVocabulary};
+    use crate::topicmodel::enums::{CsvLoadError, TopicModelVersion};
+    use crate::topicmodel::topic_model::{BasicTopicModel, BasicTopicModelWithVocabulary, TopicModel, TopicModelInferencer, TopicModelWithDocumentStats, TopicModelWithVocabulary};
     use crate::topicmodel::topic_model::SingleOrList::Single;
-    use crate::topicmodel::vocabulary::{StringVocabulary, Vocabulary, VocabularyMut};
+    use crate::topicmodel::vocabulary::{BasicVocabulary, StringVocabulary, Vocabulary, VocabularyMut};
 
 
     pub fn create_test_data() -> TopicModel<String, Vocabulary<String>> {
@@ -1256,6 +1875,279 @@ mod test {
         )
     }
 
+    #[test]
+    fn can_rank_by_relevance(){
+        let model = create_test_data();
+        let global = model.global_word_probabilities();
+        assert!((global.iter().sum::<f64>() - 1.0).abs() < 1E-10);
+
+        let scores = model.word_relevance_scores(1.0, &global).unwrap();
+        assert_eq!(model.topic_count(), scores.len());
+        assert_eq!(model.get_topic(0).unwrap()[3].ln(), scores[0][3]);
+        assert_eq!(vec![0, 1, 2], model.top_n_by_relevance(1.0, &global, 3).unwrap()[0]);
+
+        // With lambda = 0 the lift favours words that are rare in the corpus,
+        // "plane" has the highest frequency and loses its first place.
+        let top = model.top_n_by_relevance(0.0, &global, 1).unwrap();
+        assert_ne!(0, top[0][0]);
+        assert_eq!(3, model.top_n_by_relevance(0.6, &global, 3).unwrap()[1].len());
+
+        assert!(model.word_relevance_scores(1.0, &global[1..]).is_none());
+        assert!(model.top_n_by_relevance(1.0, &global[1..], 3).is_none());
+    }
+
+    #[test]
+    fn held_out_perplexity_prefers_matching_topics(){
+        let mut voc: StringVocabulary = Vocabulary::default();
+        voc.add("plane");
+        voc.add("wing");
+        voc.add("ball");
+        voc.add("goal");
+        let model = TopicModel::new(
+            vec![
+                vec![0.45, 0.45, 0.05, 0.05],
+                vec![0.05, 0.05, 0.45, 0.45],
+            ],
+            voc.clone(),
+            vec![10, 10, 10, 10],
+            vec![
+                vec![0.9, 0.1],
+                vec![0.1, 0.9],
+            ],
+            vec![10, 10]
+        );
+        let random = TopicModel::new(
+            vec![
+                vec![0.25, 0.25, 0.25, 0.25],
+                vec![0.25, 0.25, 0.25, 0.25],
+            ],
+            voc,
+            vec![10, 10, 10, 10],
+            vec![
+                vec![0.5, 0.5],
+                vec![0.5, 0.5],
+            ],
+            vec![10, 10]
+        );
+
+        let held_out = vec![(vec![0, 1], vec![5, 4])];
+        let perplexity = model.held_out_perplexity(&held_out);
+        let random_perplexity = random.held_out_perplexity(&held_out);
+        assert!(perplexity.is_finite());
+        assert!((random_perplexity - 4.0).abs() < 1E-6);
+        assert!(perplexity < random_perplexity);
+    }
+
+    #[test]
+    fn can_rank_by_frequency(){
+        const C: f64 = 1200.0;
+        let mut voc: StringVocabulary = Vocabulary::default();
+        for word_id in 0..10 {
+            voc.add(format!("word{word_id}"));
+        }
+        // The frequencies follow Zipf's law but are stored in reversed order.
+        let frequencies = (1..=10).rev().map(|rank| (C / rank as f64).round() as u64).collect_vec();
+        let model = TopicModel::new(
+            vec![vec![0.1; 10]],
+            voc.clone(),
+            frequencies,
+            vec![vec![1.0]],
+            vec![10]
+        );
+
+        let ranking = model.frequency_ranking();
+        assert_eq!((9, 1200), ranking[0]);
+        assert_eq!((0, 120), ranking[9]);
+        assert_eq!(Some(1), model.rank_of(9));
+        assert_eq!(Some(10), model.rank_of(0));
+        assert_eq!(None, model.rank_of(10));
+        assert!(model.is_approximately_zipf(0.01));
+
+        let uniform = TopicModel::new(
+            vec![vec![0.1; 10]],
+            voc,
+            vec![100; 10],
+            vec![vec![1.0]],
+            vec![10]
+        );
+        assert_eq!(Some(1), uniform.rank_of(0));
+        assert_eq!(Some(10), uniform.rank_of(9));
+        assert!(!uniform.is_approximately_zipf(0.5));
+    }
+
+    #[test]
+    fn can_drop_degenerate_topics(){
+        let mut voc: StringVocabulary = Vocabulary::default();
+        voc.add("plane");
+        voc.add("wing");
+        voc.add("deck");
+        voc.add("foil");
+        let model = TopicModel::new(
+            vec![
+                vec![0.7, 0.1, 0.1, 0.1],
+                vec![0.25, 0.25, 0.25, 0.25],
+                vec![0.97, 0.01, 0.01, 0.01],
+            ],
+            voc,
+            vec![10, 5, 5, 5],
+            vec![
+                vec![0.5, 0.25, 0.25],
+                vec![0.0, 0.5, 0.5],
+            ],
+            vec![10, 10]
+        );
+
+        let degenerate = model.degenerate_topics(0.95);
+        assert_eq!(vec![1, 2], degenerate);
+
+        let pruned = model.without_topics(&degenerate);
+        assert_eq!(1, pruned.topic_count());
+        assert_eq!(model.get_topic(0), pruned.get_topic(0));
+        assert_eq!(&vec![vec![1.0], vec![0.0]], pruned.doc_topic_distributions());
+        assert!(pruned.degenerate_topics(0.95).is_empty());
+    }
+
+    #[test]
+    fn can_prune_topics(){
+        let mut voc: StringVocabulary = Vocabulary::default();
+        voc.add("plane");
+        voc.add("wing");
+        voc.add("deck");
+        voc.add("foil");
+        let mut model = TopicModel::new(
+            vec![
+                vec![0.7, 0.1, 0.1, 0.1],
+                vec![0.25, 0.25, 0.25, 0.25],
+                vec![0.1, 0.1, 0.1, 0.7],
+                vec![0.69, 0.11, 0.1, 0.1],
+            ],
+            voc,
+            vec![10, 5, 5, 5],
+            vec![
+                vec![0.25, 0.25, 0.25, 0.25],
+            ],
+            vec![10]
+        );
+        let original = model.topics().clone();
+
+        assert_eq!(vec![1], model.prune_topics_by_entropy(1.9));
+        assert_eq!(3, model.topic_count());
+        assert_eq!(&vec![original[0].clone(), original[2].clone(), original[3].clone()], model.topics());
+        assert!(model.prune_topics_by_entropy(1.9).is_empty());
+
+        // The last topic is nearly identical to the first one.
+        assert_eq!(vec![2], model.prune_topics_by_diversity(0.01));
+        assert_eq!(&vec![original[0].clone(), original[2].clone()], model.topics());
+        assert_eq!(2, model.doc_topic_distributions()[0].len());
+    }
+
+    #[test]
+    fn resample_vocabulary_removes_frequent_words(){
+        let mut voc: StringVocabulary = Vocabulary::default();
+        voc.add("the");
+        voc.add("plane");
+        voc.add("wing");
+        let model = TopicModel::new(
+            vec![
+                vec![0.5, 0.2, 0.3],
+                vec![0.3, 0.4, 0.3],
+            ],
+            voc,
+            vec![50, 10, 40],
+            vec![
+                vec![0.5, 0.5],
+            ],
+            vec![100]
+        );
+
+        // The removal probabilities are 0.5, 0.0 and 1 - sqrt(0.3125).
+        let expected = [0.5, 0.0, 1.0 - 0.3125f64.sqrt()];
+        let runs = 2000;
+        let mut removed = [0usize; 3];
+        for seed in 0..runs {
+            let resampled = model.resample_vocabulary(0.125, seed);
+            for topic in resampled.topics() {
+                let sum = topic.iter().sum::<f64>();
+                assert!(sum == 0.0 || (sum - 1.0).abs() < 1E-10);
+            }
+            for (word_id, count) in removed.iter_mut().enumerate() {
+                if resampled.topics()[0][word_id] == 0.0 {
+                    assert_eq!(0.0, resampled.topics()[1][word_id]);
+                    *count += 1;
+                }
+            }
+        }
+        for (count, expected) in removed.iter().zip(expected.iter()) {
+            assert!((*count as f64 / runs as f64 - expected).abs() < 0.05, "{count} of {runs} does not match {expected}");
+        }
+
+        assert_eq!(model.resample_vocabulary(0.125, 42).topics(), model.resample_vocabulary(0.125, 42).topics());
+        for (original, resampled) in model.topics().iter().flatten().zip_eq(model.resample_vocabulary(1.0, 42).topics().iter().flatten()) {
+            assert!((original - resampled).abs() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn compare_topics_is_symmetric(){
+        let model = create_test_data();
+        let ab = model.compare_topics(0, 1).unwrap();
+        let ba = model.compare_topics(1, 0).unwrap();
+        assert_eq!(ab, ba);
+        assert!(ab > 0.0 && ab <= 1.0);
+        assert_eq!(Some(0.0), model.compare_topics(0, 0));
+        assert_eq!(None, model.compare_topics(0, 2));
+        assert_eq!(ab, model.diversity_score());
+    }
+
+    #[test]
+    fn can_calculate_document_frequencies(){
+        let mut voc: StringVocabulary = Vocabulary::default();
+        voc.add("plane");
+        voc.add("wing");
+        voc.add("deck");
+        let model = TopicModel::new(
+            vec![
+                vec![0.8, 0.1, 0.1],
+                vec![0.1, 0.1, 0.8],
+            ],
+            voc,
+            vec![9, 4, 9],
+            vec![
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![0.5, 0.5],
+            ],
+            vec![10, 10, 2]
+        );
+
+        // The short mixed document only contains "plane" and "deck" with an expected count of 0.9.
+        assert_eq!(vec![3, 2, 3], model.document_frequency_vector());
+
+        let tfidf = model.tfidf_score_per_topic();
+        assert_eq!(model.topic_count(), tfidf.len());
+        assert_eq!(0.8 * 2.0f64.ln(), tfidf[0][0]);
+        assert_eq!(0.1 * 2.5f64.ln(), tfidf[0][1]);
+        // "wing" is contained in fewer documents and is weighted higher.
+        assert!(tfidf[1][1] > tfidf[1][0]);
+    }
+
+    #[test]
+    fn can_select_topics(){
+        let model = create_test_data();
+        let subset = model.select_topics(&[1, 0]).unwrap();
+        assert_eq!(2, subset.topic_count());
+        assert_eq!(model.get_topic(1), subset.get_topic(0));
+        assert_eq!(model.get_topic(0), subset.get_topic(1));
+        assert_eq!(model.get_topic_meta(1).unwrap().stats.max_value, subset.get_topic_meta(0).unwrap().stats.max_value);
+        assert_eq!(Some(1), subset.original_topic_id(0));
+        assert!(std::ptr::eq(model.get_topic(1).unwrap(), subset.iter().next().unwrap()));
+        assert!(subset.get_topic(2).is_none());
+
+        let single = model.select_topics(&[1]).unwrap();
+        assert_eq!(vec![model.get_topic(1).unwrap().clone()], single.to_topics());
+        assert!(model.select_topics(&[0, 2]).is_none());
+    }
+
     #[test]
     fn can_load_and_unlad_json(){
         let topic_model = create_test_data();
@@ -1311,6 +2203,62 @@ mod test {
 
     }
 
+    #[test]
+    fn can_write_html(){
+        let model = create_test_data();
+        let mut out = Vec::new();
+        model.to_html(&mut out, 3).unwrap();
+        let html = String::from_utf8(out).unwrap();
+        assert!(html.starts_with("<table"));
+        assert!(html.contains("Topic(0)"));
+        assert!(html.contains("Topic(1)"));
+        assert_eq!(4, html.matches("<tr>").count());
+        assert!(html.contains(">plane</td>"));
+        assert!(html.contains("rgba(70, 130, 180, 1.000)"));
+    }
+
+    #[test]
+    fn can_export_and_import_top_words_as_csv(){
+        let model = create_test_data();
+        let mut out = Vec::new();
+        model.export_top_words_per_topic_csv(&mut out, 3).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        assert!(csv.starts_with("topic_id,word,probability\n0,plane,0.019\n0,aircraft,0.018\n0,airplane,0.012\n1,plane,0.02\n"));
+        assert_eq!(7, csv.lines().count());
+
+        let loaded: TopicModel<String, Vocabulary<String>> = TopicModel::import_top_words_from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(2, loaded.k());
+        let mut reexported = Vec::new();
+        loaded.export_top_words_per_topic_csv(&mut reexported, 3).unwrap();
+        assert_eq!(csv, String::from_utf8(reexported).unwrap());
+    }
+
+    #[test]
+    fn import_top_words_reports_the_position_of_errors(){
+        let loaded: TopicModel<String, Vocabulary<String>> = TopicModel::import_top_words_from_csv(
+            "topic_id,word,probability\n0,\"wing, left\",0.5\n1,\"say \"\"hi\"\"\",0.25\n".as_bytes()
+        ).unwrap();
+        assert_eq!(Some("wing, left"), loaded.vocabulary().get_value(0).map(|value| value.as_str()));
+        assert_eq!(Some("say \"hi\""), loaded.vocabulary().get_value(1).map(|value| value.as_str()));
+        assert_eq!(0.5, loaded.topics()[0][0]);
+        assert_eq!(0.25, loaded.topics()[0][1]);
+        assert_eq!(0.25, loaded.topics()[1][1]);
+        assert_eq!(0.125, loaded.topics()[1][0]);
+
+        match TopicModel::<String, Vocabulary<String>>::import_top_words_from_csv("topic_id,word,probability\n0,wing,0.5\n1,deck,high\n".as_bytes()) {
+            Err(CsvLoadError::ParseFloat { row, column, .. }) => assert_eq!((3, 3), (row, column)),
+            other => panic!("Expected a float error but got {other:?}")
+        }
+        match TopicModel::<String, Vocabulary<String>>::import_top_words_from_csv("topic_id,word,probability\n0,wing,0.5\n1000,deck,0.5\n".as_bytes()) {
+            Err(CsvLoadError::TopicIdOutOfRange { row, topic_id, topic_count }) => assert_eq!((3, 1000, 2), (row, topic_id, topic_count)),
+            other => panic!("Expected a topic id out of range but got {other:?}")
+        }
+        match TopicModel::<String, Vocabulary<String>>::import_top_words_from_csv("topic_id,word,probability\n0,wing\n".as_bytes()) {
+            Err(CsvLoadError::MissingColumn { row, column }) => assert_eq!((2, 3), (row, column)),
+            other => panic!("Expected a missing column but got {other:?}")
+        }
+    }
+
     #[test]
     fn try_infer(){
         let before = std::time::Instant::now();
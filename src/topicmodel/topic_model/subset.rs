@@ -0,0 +1,143 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::hash::Hash;
+use std::ops::Range;
+use std::sync::Arc;
+use crate::topicmodel::topic_model::{BasicTopicModel, BasicTopicModelWithVocabulary, Probability, TopicId, TopicModel, TopicModelWithDocumentStats, TopicTo, WordId, WordTo};
+use crate::topicmodel::topic_model::meta::{TopicMeta, WordMeta};
+use crate::topicmodel::vocabulary::VocabularyMut;
+
+/// A view on a subset of the topics of a topic model, without copying the topics.
+/// The topic ids of the view are the positions in the selected topics.
+///
+/// The view does not implement [BasicTopicModel] or [TopicModelWithDocumentStats], because
+/// [BasicTopicModel::topics], [BasicTopicModel::topic_metas] and [TopicModelWithDocumentStats::doc_topic_distributions]
+/// return references to the matrices of all topics, which a borrowed subset can not provide.
+/// The accessors that work on single topics are provided directly, everything that needs a
+/// complete model, like the translation, has to use the copy from [TopicSubset::to_topic_model].
+#[derive(Debug, Clone)]
+pub struct TopicSubset<'a, M: ?Sized> {
+    topic_model: &'a M,
+    topic_ids: Vec<TopicId>
+}
+
+impl<'a, M> TopicSubset<'a, M> where M: BasicTopicModel + ?Sized {
+    /// Creates the view for `topic_ids` in the given order.
+    /// Returns None if any of the topic ids is not in `topic_model`.
+    pub fn new(topic_model: &'a M, topic_ids: &[TopicId]) -> Option<Self> {
+        if topic_ids.iter().all(|topic_id| topic_model.contains_topic_id(*topic_id)) {
+            Some(Self { topic_model, topic_ids: topic_ids.to_vec() })
+        } else {
+            None
+        }
+    }
+
+    /// The underlying topic model
+    pub fn topic_model(&self) -> &'a M {
+        self.topic_model
+    }
+
+    /// The selected topic ids of the underlying topic model
+    pub fn selected_topic_ids(&self) -> &[TopicId] {
+        &self.topic_ids
+    }
+
+    /// The topic id in the underlying topic model for `topic_id`
+    pub fn original_topic_id(&self, topic_id: TopicId) -> Option<TopicId> {
+        self.topic_ids.get(topic_id).copied()
+    }
+
+    /// The number of selected topics
+    pub fn topic_count(&self) -> usize {
+        self.topic_ids.len()
+    }
+
+    /// The size of the vocabulary
+    pub fn vocabulary_size(&self) -> usize {
+        self.topic_model.vocabulary_size()
+    }
+
+    /// A range over all topicIds of the view
+    pub fn topic_ids(&self) -> Range<TopicId> {
+        0..self.topic_ids.len()
+    }
+
+    /// Returns true if the `topic_id` is contained in the view
+    pub fn contains_topic_id(&self, topic_id: TopicId) -> bool {
+        topic_id < self.topic_ids.len()
+    }
+
+    /// Get the topic for `topic_id`
+    pub fn get_topic(&self, topic_id: TopicId) -> Option<&'a WordTo<Probability>> {
+        self.topic_model.get_topic(self.original_topic_id(topic_id)?)
+    }
+
+    /// Get the `TopicMeta` for `topic_id`
+    pub fn get_topic_meta(&self, topic_id: TopicId) -> Option<&'a TopicMeta> {
+        self.topic_model.get_topic_meta(self.original_topic_id(topic_id)?)
+    }
+
+    /// Get the probability of `word_id` of `topic_id`
+    pub fn get_probability(&self, topic_id: TopicId, word_id: WordId) -> Option<&'a Probability> {
+        self.topic_model.get_probability(self.original_topic_id(topic_id)?, word_id)
+    }
+
+    /// Get the [WordMeta] of `word_id` of `topic_id`
+    pub fn get_word_meta(&self, topic_id: TopicId, word_id: WordId) -> Option<&'a Arc<WordMeta>> {
+        self.topic_model.get_word_meta(self.original_topic_id(topic_id)?, word_id)
+    }
+
+    /// Iterates over the selected topics in the selected order
+    pub fn iter(&self) -> impl Iterator<Item=&'a WordTo<Probability>> + '_ {
+        self.topic_ids.iter().map(|topic_id| self.topic_model.get_topic(*topic_id).unwrap())
+    }
+
+    /// Iterates over the metas of the selected topics in the selected order
+    pub fn iter_metas(&self) -> impl Iterator<Item=&'a TopicMeta> + '_ {
+        self.topic_ids.iter().map(|topic_id| self.topic_model.get_topic_meta(*topic_id).unwrap())
+    }
+
+    /// Copies the selected topics.
+    pub fn to_topics(&self) -> TopicTo<WordTo<Probability>> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'a, M> TopicSubset<'a, M> where M: BasicTopicModel + TopicModelWithDocumentStats + ?Sized {
+    /// Copies the selected topics into a [TopicModel] with the vocabulary of the underlying model.
+    /// The document topic distributions only keep the probabilities of the selected topics, use
+    /// this to translate or store a subset.
+    ///
+    /// This is not a view: the selected topics, their document topic distributions, the complete
+    /// vocabulary and the document statistics are cloned.
+    pub fn to_topic_model<T, Voc>(&self) -> TopicModel<T, Voc> where
+        M: BasicTopicModelWithVocabulary<T, Voc>,
+        T: Hash + Eq + Ord,
+        Voc: VocabularyMut<T> + Clone
+    {
+        let doc_topic_distributions = self.topic_model
+            .doc_topic_distributions()
+            .iter()
+            .map(|distribution| self.topic_ids.iter().map(|topic_id| distribution[*topic_id]).collect())
+            .collect();
+        TopicModel::new(
+            self.to_topics(),
+            self.topic_model.vocabulary().clone(),
+            self.topic_model.used_vocab_frequency().clone(),
+            doc_topic_distributions,
+            self.topic_model.document_lengths().clone()
+        )
+    }
+}
@@ -20,7 +20,7 @@ use std::hash::{Hash};
 use std::collections::hash_map::{Entry};
 use std::fmt::{Debug, Display, Formatter};
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::marker::PhantomData;
 use std::ops::{Bound, Deref, Range};
 use std::path::Path;
@@ -99,7 +99,8 @@ pub trait BasicVocabulary<T>: Send + Sync + AsRef<Vec<HashRef<T>>> {
     /// Creates a new instance
     fn create(language: Option<LanguageHint>) -> Self where Self: Sized;
 
-    /// Creates a new instance
+    /// Creates a new instance with the ids of the positions in `voc`.
+    /// A duplicated value keeps all of its ids, but is resolved to the id of its last occurrence.
     fn create_from(language: Option<LanguageHint>, voc: Vec<T>) -> Self where Self: Sized, T: Eq + Hash;
 
     /// Creates a trie from this
@@ -210,6 +211,7 @@ pub trait LoadableVocabulary<T, E> where T: Hash + Eq + FromStr<Err=E>, E: Debug
 #[derive(Clone, Debug)]
 pub struct Vocabulary<T> {
     language: Option<LanguageHint>,
+    /// Shorter than `id2entry` if [BasicVocabulary::create_from] got duplicated values.
     entry2id: HashMap<HashRef<T>, usize>,
     id2entry: Vec<HashRef<T>>
 }
@@ -293,8 +295,24 @@ impl <T> BasicVocabulary<T> for Vocabulary<T> {
 
 
     fn create_from(language: Option<LanguageHint>, voc: Vec<T>) -> Self where Self: Sized, T: Eq + Hash {
-        let id2entry = voc.into_iter().map(|value| HashRef::new(value)).collect_vec();
-        let entry2id = id2entry.iter().cloned().enumerate().map(|(a, b)| (b, a)).collect();
+        // Duplicated values share the allocation of their first occurrence,
+        // but are resolved to the id of their last occurrence.
+        let mut id2entry: Vec<HashRef<T>> = Vec::with_capacity(voc.len());
+        let mut entry2id = HashMap::with_capacity(voc.len());
+        for value in voc.into_iter() {
+            let value = HashRef::new(value);
+            match entry2id.entry(value) {
+                Entry::Occupied(mut entry) => {
+                    let interned = id2entry[*entry.get()].clone();
+                    id2entry.push(interned);
+                    entry.insert(id2entry.len() - 1);
+                }
+                Entry::Vacant(entry) => {
+                    id2entry.push(entry.key().clone());
+                    entry.insert(id2entry.len() - 1);
+                }
+            }
+        }
         Self {
             language,
             id2entry,
@@ -474,6 +492,181 @@ pub enum LoadVocabularyError<E: Debug> {
 impl<T: Hash + Eq + FromStr<Err=E>, E: Debug> LoadableVocabulary<T, E> for  Vocabulary<T> {
 }
 
+/// Errors when loading a vocabulary from pretrained embeddings.
+#[derive(Debug, Error)]
+pub enum EmbeddingLoadError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("The embeddings are missing the \"vocab_size dim\" header!")]
+    MissingHeader,
+    #[error("The header \"{0}\" is not of the form \"vocab_size dim\"!")]
+    MalformedHeader(String),
+    #[error("The vector in line {line} has {actual} dimensions but {expected} were expected!")]
+    WrongDimension { line: usize, expected: usize, actual: usize },
+    #[error("The value \"{value}\" in line {line} is not a float!")]
+    MalformedFloat { line: usize, value: String },
+    #[error("The word \"{word}\" in line {line} was already defined!")]
+    DuplicateWord { line: usize, word: String },
+    #[error("Expected {expected} words but found {actual}!")]
+    WrongVocabularySize { expected: usize, actual: usize },
+}
+
+/// Errors when reading a vocabulary written by [Vocabulary::write_binary].
+#[derive(Debug, Error)]
+pub enum BinaryVocabError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("Expected the magic number {expected:?} but got {0:?}!", expected = BINARY_VOCABULARY_MAGIC)]
+    IllegalMagicNumber([u8; 4]),
+    #[error("The version {0} is not supported!")]
+    UnsupportedVersion(u16),
+    #[error("The entry {index} is not valid UTF-8: {err}")]
+    Utf8 {
+        index: usize,
+        #[source]
+        err: std::string::FromUtf8Error
+    },
+    #[error("The language is not valid UTF-8: {0}")]
+    LanguageUtf8(#[source] std::string::FromUtf8Error),
+}
+
+/// The magic number of the binary vocabulary format.
+const BINARY_VOCABULARY_MAGIC: [u8; 4] = *b"LDTV";
+/// The current version of the binary vocabulary format.
+const BINARY_VOCABULARY_VERSION: u16 = 1;
+/// The length of a missing language.
+const BINARY_VOCABULARY_NO_LANGUAGE: u32 = u32::MAX;
+
+fn write_length_prefixed(writer: &mut impl Write, value: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(value.len()).map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "The entry is too long!"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(value)
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_bytes(reader: &mut impl Read, len: u32) -> std::io::Result<Vec<u8>> {
+    // The length is not trusted for the allocation, a corrupted file may claim any size.
+    let mut buf = Vec::new();
+    let read = reader.by_ref().take(len as u64).read_to_end(&mut buf)?;
+    if read != len as usize {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, format!("Expected {len} bytes but got {read}!")))
+    }
+    Ok(buf)
+}
+
+impl Vocabulary<String> {
+    /// Writes the vocabulary in a compact binary format, all numbers are little endian:
+    /// the magic number `LDTV`, a 2-byte version, the language as 4-byte length prefixed
+    /// UTF-8 (`u32::MAX` if there is none), the 4-byte count of the entries and each entry
+    /// as 4-byte length prefixed UTF-8.
+    pub fn write_binary<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writer.write_all(&BINARY_VOCABULARY_MAGIC)?;
+        writer.write_all(&BINARY_VOCABULARY_VERSION.to_le_bytes())?;
+        match self.language {
+            None => writer.write_all(&BINARY_VOCABULARY_NO_LANGUAGE.to_le_bytes())?,
+            Some(ref language) => write_length_prefixed(&mut writer, language.as_str().as_bytes())?
+        }
+        let count = u32::try_from(self.id2entry.len()).map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "The vocabulary is too big!"))?;
+        writer.write_all(&count.to_le_bytes())?;
+        for entry in self.id2entry.iter() {
+            write_length_prefixed(&mut writer, entry.as_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a vocabulary written by [Vocabulary::write_binary].
+    pub fn from_binary_reader<R: Read>(reader: R) -> Result<Vocabulary<String>, BinaryVocabError> {
+        let mut reader = BufReader::new(reader);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != BINARY_VOCABULARY_MAGIC {
+            return Err(BinaryVocabError::IllegalMagicNumber(magic))
+        }
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != BINARY_VOCABULARY_VERSION {
+            return Err(BinaryVocabError::UnsupportedVersion(version))
+        }
+        let language = match read_u32(&mut reader)? {
+            BINARY_VOCABULARY_NO_LANGUAGE => None,
+            len => Some(LanguageHint::new(
+                String::from_utf8(read_bytes(&mut reader, len)?).map_err(BinaryVocabError::LanguageUtf8)?
+            ))
+        };
+        let count = read_u32(&mut reader)? as usize;
+        // The count is not trusted for the allocation, a corrupted file may claim any size.
+        let mut entries = Vec::with_capacity(count.min(1 << 20));
+        for index in 0..count {
+            let len = read_u32(&mut reader)?;
+            let entry = String::from_utf8(read_bytes(&mut reader, len)?).map_err(|err| BinaryVocabError::Utf8 { index, err })?;
+            entries.push(entry);
+        }
+        Ok(Vocabulary::create_from(language, entries))
+    }
+
+    /// Loads a vocabulary and the associated vectors from the word2vec/fastText text format.
+    /// The first line is the header `vocab_size dim`, followed by one `word v_1 ... v_dim` per line.
+    /// The ids of the vocabulary are the positions of the vectors.
+    pub fn from_pretrained_text_format<R: BufRead>(reader: R) -> Result<(Vocabulary<String>, Vec<Vec<f32>>), EmbeddingLoadError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(EmbeddingLoadError::MissingHeader)??;
+        let (vocab_size, dim) = match header.split_whitespace().collect_tuple() {
+            Some((vocab_size, dim)) => {
+                match (vocab_size.parse::<usize>(), dim.parse::<usize>()) {
+                    (Ok(vocab_size), Ok(dim)) => (vocab_size, dim),
+                    _ => return Err(EmbeddingLoadError::MalformedHeader(header))
+                }
+            }
+            None => return Err(EmbeddingLoadError::MalformedHeader(header))
+        };
+
+        // The header is not trusted, a wrong size is reported after reading the entries.
+        let capacity = vocab_size.min(1 << 20);
+        let mut vocabulary = Vocabulary::with_capacity(None, capacity);
+        let mut vectors = Vec::with_capacity(capacity);
+        for (idx, line) in lines.enumerate() {
+            let line = line?;
+            // The header is line 1.
+            let line_no = idx + 2;
+            let mut parts = line.split_whitespace();
+            let word = match parts.next() {
+                None => continue,
+                Some(word) => word
+            };
+            let vector = parts.map(|value| {
+                value.parse::<f32>().map_err(|_| EmbeddingLoadError::MalformedFloat { line: line_no, value: value.to_string() })
+            }).collect::<Result<Vec<_>, _>>()?;
+            if vector.len() != dim {
+                return Err(EmbeddingLoadError::WrongDimension { line: line_no, expected: dim, actual: vector.len() })
+            }
+            if vocabulary.contains(word) {
+                return Err(EmbeddingLoadError::DuplicateWord { line: line_no, word: word.to_string() })
+            }
+            vocabulary.add(word);
+            vectors.push(vector);
+        }
+
+        if vectors.len() != vocab_size {
+            return Err(EmbeddingLoadError::WrongVocabularySize { expected: vocab_size, actual: vectors.len() })
+        }
+
+        Ok((vocabulary, vectors))
+    }
+
+    /// Retrieves the id of the embedding vector for `word`, alias for [SearchableVocabulary::get_id].
+    #[inline(always)]
+    pub fn get_embedding_id(&self, word: &str) -> Option<usize> {
+        self.get_id(word)
+    }
+}
+
 
 impl<T: ToParseableString> StoreableVocabulary<T> for Vocabulary<T>  {
     /// Writes the vocabulary to `writer` in the list format
@@ -699,7 +892,8 @@ impl<K, Q> Borrow<Wrapper<Q>> for HashRef<K>
 
 #[cfg(test)]
 mod test {
-    use crate::topicmodel::vocabulary::{HashRef, StringVocabulary, BasicVocabulary, Vocabulary, VocabularyMut, SearchableVocabulary};
+    use std::io::Cursor;
+    use crate::topicmodel::vocabulary::{HashRef, StringVocabulary, BasicVocabulary, Vocabulary, VocabularyMut, SearchableVocabulary, EmbeddingLoadError, BinaryVocabError, BINARY_VOCABULARY_MAGIC, BINARY_VOCABULARY_NO_LANGUAGE, BINARY_VOCABULARY_VERSION};
 
     #[test]
     fn can_insert_and_retrieve() {
@@ -718,6 +912,77 @@ mod test {
         println!("{voc2}");
     }
 
+    #[test]
+    fn deserialization_interns_duplicated_words() {
+        let words = (0..1000).map(|value| format!("word{}", value % 500)).collect::<Vec<_>>();
+        let voc: Vocabulary<String> = Vocabulary::from(words);
+        let s = serde_json::to_string(&voc).unwrap();
+        let voc2: Vocabulary<String> = serde_json::from_str(&s).unwrap();
+
+        assert_eq!(1000, voc2.id2entry.len());
+        assert_eq!(500, voc2.entry2id.len());
+        let mut pool: Vec<&HashRef<String>> = Vec::new();
+        for entry in voc2.id2entry.iter() {
+            if !pool.iter().any(|known| HashRef::ptr_eq(known, entry)) {
+                pool.push(entry);
+            }
+        }
+        assert_eq!(500, pool.len());
+        assert_eq!(Some(507), voc2.get_id("word7"));
+        assert!(HashRef::ptr_eq(&voc2.id2entry[7], &voc2.id2entry[507]));
+
+        let reference: HashRef<String> = serde_json::from_str(&serde_json::to_string(&voc2.id2entry[7]).unwrap()).unwrap();
+        assert_eq!(voc2.id2entry[7], reference);
+    }
+
+    #[test]
+    fn can_write_and_read_binary_vocabularies() {
+        let mut voc = StringVocabulary::new_for("MyLang");
+        voc.add("Hello World".to_string());
+        voc.add("Flugzeug".to_string());
+        voc.add("Überflügel".to_string());
+        voc.add("飞机".to_string());
+        voc.add("".to_string());
+
+        let mut out = Vec::new();
+        voc.write_binary(&mut out).unwrap();
+        let voc2 = Vocabulary::from_binary_reader(out.as_slice()).unwrap();
+        assert_eq!(voc, voc2);
+        assert_eq!(voc.language(), voc2.language());
+        assert_eq!(Some(3), voc2.get_id("飞机"));
+
+        let mut without_language = Vocabulary::<String>::default();
+        without_language.add("Ebene".to_string());
+        let mut out_without_language = Vec::new();
+        without_language.write_binary(&mut out_without_language).unwrap();
+        let reloaded = Vocabulary::from_binary_reader(out_without_language.as_slice()).unwrap();
+        assert_eq!(without_language, reloaded);
+        assert_eq!(None, reloaded.language());
+
+        let mut wrong_magic = out.clone();
+        wrong_magic[0] = b'X';
+        assert!(matches!(Vocabulary::from_binary_reader(wrong_magic.as_slice()), Err(BinaryVocabError::IllegalMagicNumber(_))));
+
+        let mut wrong_version = out.clone();
+        wrong_version[4] = 99;
+        assert!(matches!(Vocabulary::from_binary_reader(wrong_version.as_slice()), Err(BinaryVocabError::UnsupportedVersion(99))));
+
+        assert!(matches!(Vocabulary::from_binary_reader(&out[..out.len() - 3]), Err(BinaryVocabError::IO(_))));
+
+        // A claimed length beyond the end of the data fails without allocating it.
+        let mut huge_entry = Vec::new();
+        huge_entry.extend_from_slice(&BINARY_VOCABULARY_MAGIC);
+        huge_entry.extend_from_slice(&BINARY_VOCABULARY_VERSION.to_le_bytes());
+        huge_entry.extend_from_slice(&BINARY_VOCABULARY_NO_LANGUAGE.to_le_bytes());
+        huge_entry.extend_from_slice(&1u32.to_le_bytes());
+        huge_entry.extend_from_slice(&(u32::MAX - 1).to_le_bytes());
+        huge_entry.extend_from_slice(b"short");
+        match Vocabulary::from_binary_reader(huge_entry.as_slice()) {
+            Err(BinaryVocabError::IO(err)) => assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind()),
+            other => panic!("Expected an unexpected eof but got {other:?}")
+        }
+    }
+
     #[test]
     fn equals_behaves_normally() {
         let a = HashRef::new("Test1");
@@ -733,4 +998,35 @@ mod test {
         assert_ne!(d, b);
         assert_ne!(d, c);
     }
+
+    #[test]
+    fn can_load_pretrained_text_format() {
+        let data = "3 2\nhello 0.1 0.2\nworld -1 2.5e-1\nfoo 3 4\n";
+        let (voc, vectors) = StringVocabulary::from_pretrained_text_format(Cursor::new(data)).unwrap();
+        assert_eq!(3, voc.len());
+        assert_eq!(Some(1), voc.get_embedding_id("world"));
+        assert_eq!(Some("foo"), voc.get_value(2).map(|x| x.as_str()));
+        assert_eq!(vec![vec![0.1f32, 0.2], vec![-1.0, 0.25], vec![3.0, 4.0]], vectors);
+
+        assert!(matches!(
+            StringVocabulary::from_pretrained_text_format(Cursor::new("1 3\nhello 0.1 0.2\n")),
+            Err(EmbeddingLoadError::WrongDimension { line: 2, expected: 3, actual: 2 })
+        ));
+        assert!(matches!(
+            StringVocabulary::from_pretrained_text_format(Cursor::new("1 2\nhello 0.1 abc\n")),
+            Err(EmbeddingLoadError::MalformedFloat { line: 2, .. })
+        ));
+        assert!(matches!(
+            StringVocabulary::from_pretrained_text_format(Cursor::new("hello 0.1 0.2\n")),
+            Err(EmbeddingLoadError::MalformedHeader(_))
+        ));
+        assert!(matches!(
+            StringVocabulary::from_pretrained_text_format(Cursor::new("2 2\nhello 0.1 0.2\n")),
+            Err(EmbeddingLoadError::WrongVocabularySize { expected: 2, actual: 1 })
+        ));
+        assert!(matches!(
+            StringVocabulary::from_pretrained_text_format(Cursor::new("18446744073709551615 2\nhello 0.1 0.2\n")),
+            Err(EmbeddingLoadError::WrongVocabularySize { expected: usize::MAX, actual: 1 })
+        ));
+    }
 }
\ No newline at end of file
@@ -20,30 +20,36 @@ use std::hash::Hash;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::ops::Deref;
+use std::sync::Arc;
 use evalexpr::{Context, context_map, ContextWithMutableVariables, EmptyContextWithBuiltinFunctions, HashMapContext, IterateVariablesContext};
 use itertools::{Itertools};
 use rayon::prelude::*;
-use strum::{AsRefStr, Display, EnumString, ParseError};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString, ParseError, VariantArray};
 use thiserror::Error;
 use crate::toolkit::evalexpr::{CombineableContext};
 use crate::topicmodel::topic_model::{BasicTopicModel, TopicModel, TopicModelWithDocumentStats, TopicModelWithVocabulary};
-use crate::topicmodel::dictionary::{DictionaryMut, DictionaryWithVocabulary, FromVoc};
-use crate::topicmodel::dictionary::direction::{AToB, B, BToA};
+use crate::topicmodel::topic_model::meta::WordMeta;
+use crate::topicmodel::dictionary::{BasicDictionary, DictionaryMut, DictionaryWithVocabulary, FromVoc};
+use crate::topicmodel::dictionary::direction::{AToB, A, B, BToA};
 use crate::topicmodel::vocabulary::{MappableVocabulary, BasicVocabulary, Vocabulary, VocabularyMut, SearchableVocabulary};
 use crate::translate::LanguageOrigin::{Origin, Target};
 use crate::variable_names::*;
 use crate::voting::{VotingExpressionError, VotingMethod, VotingResult};
 use crate::voting::traits::VotingMethodMarker;
+use crate::voting::BuildInVoting;
 use pyo3::{Bound, pyclass, pymethods, PyResult};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::{PyModule, PyModuleMethods};
 use crate::external_variable_provider::{AsVariableProvider, AsVariableProviderError, VariableProvider, VariableProviderError, VariableProviderOut};
-use crate::topicmodel::create_topic_model_specific_dictionary;
+use crate::topicmodel::ToTopicModelSpecific;
 use crate::topicmodel::language_hint::LanguageHint;
+use crate::topicmodel::reference::HashRef;
+use crate::toolkit::memory::MemoryReporter;
 use crate::translate::TranslateError::IncompatibleLanguages;
 
 /// The config for a translation
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TranslateConfig<V: VotingMethodMarker> {
     /// The voting to be used
     voting: V,
@@ -55,11 +61,122 @@ pub struct TranslateConfig<V: VotingMethodMarker> {
     keep_original_word: KeepOriginalWord,
     /// Limits the number of accepted candidates to N. If not set keep all.
     top_candidate_limit: Option<NonZeroUsize>,
+    /// An external weight for each voter, indexed by the word id in language A.
+    /// Voters without a weight get a weight of 1.0.
+    voter_weights: Option<Arc<Vec<f64>>>,
+    /// If set, the words in language A without any candidate in all topics are collected.
+    collect_untranslated: bool,
+    /// If set, the topic model specific dictionary used for the translation is retained.
+    keep_topic_specific_dictionary: bool,
+    /// The smoothing constant k of the reciprocal ranks, they are calculated as `1/(rank+k)`.
+    rank_smoothing: f64,
+    /// The normalization of the candidate scores of each topic before filling the missing words with epsilon.
+    score_normalization: ScoreNormalization,
+    /// Limits the voters of each candidate to the N voters with the highest probability. If not set keep all.
+    per_candidate_voter_limit: Option<NonZeroUsize>,
+    /// The minimal fraction of the words of the topic model that have to be in the dictionary.
+    /// If not set only a dictionary without any of the words is rejected.
+    min_vocabulary_overlap: Option<f64>,
+    /// The levels of the translation that are processed in parallel.
+    parallelism: ParallelismMode,
+    /// If set, candidates equal to the translated word are skipped.
+    exclude_self: bool,
 }
 
 impl<V> TranslateConfig<V> where V: VotingMethodMarker {
     pub fn new(voting: V, epsilon: Option<f64>, threshold: Option<f64>, keep_original_word: KeepOriginalWord, top_candidate_limit: Option<NonZeroUsize>) -> Self {
-        Self { epsilon, voting, threshold, keep_original_word, top_candidate_limit }
+        Self { epsilon, voting, threshold, keep_original_word, top_candidate_limit, voter_weights: None, collect_untranslated: false, keep_topic_specific_dictionary: false, rank_smoothing: 0.0, score_normalization: ScoreNormalization::None, per_candidate_voter_limit: None, min_vocabulary_overlap: None, parallelism: ParallelismMode::Both, exclude_self: false }
+    }
+
+    /// Sets the weights of the voters, indexed by the word id in language A.
+    pub fn with_voter_weights(mut self, voter_weights: Vec<f64>) -> Self {
+        self.voter_weights = Some(Arc::new(voter_weights));
+        self
+    }
+
+    /// Enables or disables the collection of the untranslated words.
+    pub fn with_untranslated_report(mut self, collect_untranslated: bool) -> Self {
+        self.collect_untranslated = collect_untranslated;
+        self
+    }
+
+    /// Enables or disables retaining the topic model specific dictionary used for the translation.
+    pub fn with_topic_specific_dictionary(mut self, keep_topic_specific_dictionary: bool) -> Self {
+        self.keep_topic_specific_dictionary = keep_topic_specific_dictionary;
+        self
+    }
+
+    /// Sets the smoothing constant k of the reciprocal ranks. A k greater than 0
+    /// reduces the dominance of the top ranked voters.
+    pub fn with_rank_smoothing(mut self, rank_smoothing: f64) -> Self {
+        self.rank_smoothing = rank_smoothing;
+        self
+    }
+
+    /// Sets the normalization of the candidate scores of each topic.
+    pub fn with_score_normalization(mut self, score_normalization: ScoreNormalization) -> Self {
+        self.score_normalization = score_normalization;
+        self
+    }
+
+    /// Limits the voters of each candidate to the `per_candidate_voter_limit` voters with the highest probability.
+    pub fn with_per_candidate_voter_limit(mut self, per_candidate_voter_limit: Option<NonZeroUsize>) -> Self {
+        self.per_candidate_voter_limit = per_candidate_voter_limit;
+        self
+    }
+
+    /// Sets the minimal fraction of the words of the topic model that have to be in the dictionary.
+    pub fn with_min_vocabulary_overlap(mut self, min_vocabulary_overlap: Option<f64>) -> Self {
+        self.min_vocabulary_overlap = min_vocabulary_overlap;
+        self
+    }
+
+    /// Sets the levels of the translation that are processed in parallel.
+    pub fn with_parallelism(mut self, parallelism: ParallelismMode) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Enables or disables skipping candidates that are equal to the translated word.
+    pub fn with_exclude_self(mut self, exclude_self: bool) -> Self {
+        self.exclude_self = exclude_self;
+        self
+    }
+
+    /// Creates a builder for a config with `voting`.
+    pub fn builder(voting: V) -> TranslateConfigBuilder<V> {
+        TranslateConfigBuilder::new().voting(voting)
+    }
+
+    /// Checks the constraints of the config.
+    pub fn validate(&self) -> Result<(), TranslateConfigError> {
+        if let Some(epsilon) = self.epsilon {
+            if epsilon.is_nan() || epsilon <= 0.0 {
+                return Err(TranslateConfigError::InvalidEpsilon(epsilon))
+            }
+        }
+        if let Some(threshold) = self.threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(TranslateConfigError::InvalidThreshold(threshold))
+            }
+        }
+        if let Some(min_vocabulary_overlap) = self.min_vocabulary_overlap {
+            if !(0.0..=1.0).contains(&min_vocabulary_overlap) {
+                return Err(TranslateConfigError::InvalidMinVocabularyOverlap(min_vocabulary_overlap))
+            }
+        }
+        if self.rank_smoothing.is_nan() || self.rank_smoothing < 0.0 {
+            return Err(TranslateConfigError::InvalidRankSmoothing(self.rank_smoothing))
+        }
+        Ok(())
+    }
+
+    /// The weight of the voter with `word_id`, defaults to 1.0.
+    fn voter_weight(&self, word_id: usize) -> f64 {
+        self.voter_weights
+            .as_ref()
+            .and_then(|weights| weights.get(word_id).copied())
+            .unwrap_or(1.0)
     }
 }
 
@@ -70,16 +187,309 @@ impl<'a, V> Clone for TranslateConfig<V> where V: VotingMethodMarker + Clone {
             epsilon: self.epsilon,
             threshold: self.threshold,
             keep_original_word: self.keep_original_word,
-            top_candidate_limit: self.top_candidate_limit
+            top_candidate_limit: self.top_candidate_limit,
+            voter_weights: self.voter_weights.clone(),
+            collect_untranslated: self.collect_untranslated,
+            keep_topic_specific_dictionary: self.keep_topic_specific_dictionary,
+            rank_smoothing: self.rank_smoothing,
+            score_normalization: self.score_normalization,
+            per_candidate_voter_limit: self.per_candidate_voter_limit,
+            min_vocabulary_overlap: self.min_vocabulary_overlap,
+            parallelism: self.parallelism,
+            exclude_self: self.exclude_self
         }
     }
 }
 
 
+/// An error of an invalid [TranslateConfig].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum TranslateConfigError {
+    #[error("No voting was set!")]
+    MissingVoting,
+    #[error("The epsilon has to be positive but was {0}!")]
+    InvalidEpsilon(f64),
+    #[error("The threshold has to be in [0, 1] but was {0}!")]
+    InvalidThreshold(f64),
+    #[error("The top candidate limit has to be at least 1!")]
+    InvalidTopCandidateLimit,
+    #[error("The per candidate voter limit has to be at least 1!")]
+    InvalidPerCandidateVoterLimit,
+    #[error("The minimal vocabulary overlap has to be in [0, 1] but was {0}!")]
+    InvalidMinVocabularyOverlap(f64),
+    #[error("The rank smoothing has to be at least 0 but was {0}!")]
+    InvalidRankSmoothing(f64),
+}
+
+/// A builder for a [TranslateConfig], validates the config on [TranslateConfigBuilder::build].
+#[derive(Debug, Clone)]
+pub struct TranslateConfigBuilder<V> {
+    voting: Option<V>,
+    epsilon: Option<f64>,
+    threshold: Option<f64>,
+    keep_original_word: KeepOriginalWord,
+    top_candidate_limit: Option<usize>,
+    voter_weights: Option<Vec<f64>>,
+    collect_untranslated: bool,
+    keep_topic_specific_dictionary: bool,
+    rank_smoothing: f64,
+    score_normalization: ScoreNormalization,
+    per_candidate_voter_limit: Option<usize>,
+    min_vocabulary_overlap: Option<f64>,
+    parallelism: ParallelismMode,
+    exclude_self: bool,
+}
+
+impl<V> Default for TranslateConfigBuilder<V> {
+    fn default() -> Self {
+        Self {
+            voting: None,
+            epsilon: None,
+            threshold: None,
+            keep_original_word: KeepOriginalWord::default(),
+            top_candidate_limit: None,
+            voter_weights: None,
+            collect_untranslated: false,
+            keep_topic_specific_dictionary: false,
+            rank_smoothing: 0.0,
+            score_normalization: ScoreNormalization::None,
+            per_candidate_voter_limit: None,
+            min_vocabulary_overlap: None,
+            parallelism: ParallelismMode::default(),
+            exclude_self: false
+        }
+    }
+}
+
+impl<V> TranslateConfigBuilder<V> where V: VotingMethodMarker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn voting(mut self, voting: V) -> Self {
+        self.voting = Some(voting);
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: Option<f64>) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: Option<f64>) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn keep_original_word(mut self, keep_original_word: KeepOriginalWord) -> Self {
+        self.keep_original_word = keep_original_word;
+        self
+    }
+
+    pub fn top_candidate_limit(mut self, top_candidate_limit: Option<usize>) -> Self {
+        self.top_candidate_limit = top_candidate_limit;
+        self
+    }
+
+    pub fn voter_weights(mut self, voter_weights: Option<Vec<f64>>) -> Self {
+        self.voter_weights = voter_weights;
+        self
+    }
+
+    pub fn collect_untranslated(mut self, collect_untranslated: bool) -> Self {
+        self.collect_untranslated = collect_untranslated;
+        self
+    }
+
+    pub fn keep_topic_specific_dictionary(mut self, keep_topic_specific_dictionary: bool) -> Self {
+        self.keep_topic_specific_dictionary = keep_topic_specific_dictionary;
+        self
+    }
+
+    pub fn rank_smoothing(mut self, rank_smoothing: f64) -> Self {
+        self.rank_smoothing = rank_smoothing;
+        self
+    }
+
+    pub fn score_normalization(mut self, score_normalization: ScoreNormalization) -> Self {
+        self.score_normalization = score_normalization;
+        self
+    }
+
+    pub fn per_candidate_voter_limit(mut self, per_candidate_voter_limit: Option<usize>) -> Self {
+        self.per_candidate_voter_limit = per_candidate_voter_limit;
+        self
+    }
+
+    pub fn min_vocabulary_overlap(mut self, min_vocabulary_overlap: Option<f64>) -> Self {
+        self.min_vocabulary_overlap = min_vocabulary_overlap;
+        self
+    }
+
+    pub fn parallelism(mut self, parallelism: ParallelismMode) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    pub fn exclude_self(mut self, exclude_self: bool) -> Self {
+        self.exclude_self = exclude_self;
+        self
+    }
+
+    /// Builds and validates the config.
+    pub fn build(self) -> Result<TranslateConfig<V>, TranslateConfigError> {
+        let top_candidate_limit = match self.top_candidate_limit {
+            None => None,
+            Some(limit) => Some(NonZeroUsize::new(limit).ok_or(TranslateConfigError::InvalidTopCandidateLimit)?)
+        };
+        let per_candidate_voter_limit = match self.per_candidate_voter_limit {
+            None => None,
+            Some(limit) => Some(NonZeroUsize::new(limit).ok_or(TranslateConfigError::InvalidPerCandidateVoterLimit)?)
+        };
+        let config = TranslateConfig {
+            voting: self.voting.ok_or(TranslateConfigError::MissingVoting)?,
+            epsilon: self.epsilon,
+            threshold: self.threshold,
+            keep_original_word: self.keep_original_word,
+            top_candidate_limit,
+            voter_weights: self.voter_weights.map(Arc::new),
+            collect_untranslated: self.collect_untranslated,
+            keep_topic_specific_dictionary: self.keep_topic_specific_dictionary,
+            rank_smoothing: self.rank_smoothing,
+            score_normalization: self.score_normalization,
+            per_candidate_voter_limit,
+            min_vocabulary_overlap: self.min_vocabulary_overlap,
+            parallelism: self.parallelism,
+            exclude_self: self.exclude_self
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+
+/// Named starting points for a [TranslateConfig].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(AsRefStr, Display, EnumString, VariantArray, Serialize, Deserialize)]
+pub enum Preset {
+    /// Sums up the scores of the voters and keeps only the best candidate.
+    FastBaseline,
+    /// Combines the scores and reciprocal ranks of the voters, keeps the top 3 candidates
+    /// and the original word if there is no translation.
+    Balanced,
+    /// Keeps all candidates and the original word, the reciprocal ranks are smoothed
+    /// to reduce the dominance of the top ranked voters.
+    HighRecall,
+}
+
+impl TranslateConfig<BuildInVoting> {
+    /// Creates the config for `preset`.
+    pub fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::FastBaseline => {
+                Self::new(BuildInVoting::CombSum, None, None, KeepOriginalWord::Never, NonZeroUsize::new(1))
+            }
+            Preset::Balanced => {
+                Self::new(BuildInVoting::PCombSum, None, None, KeepOriginalWord::IfNoTranslation, NonZeroUsize::new(3))
+            }
+            Preset::HighRecall => {
+                Self::new(BuildInVoting::CombSumRR, None, None, KeepOriginalWord::Always, None)
+                    .with_rank_smoothing(10.0)
+            }
+        }
+    }
+}
+
+
+/// The levels of a translation that are processed in parallel.
+///
+/// All modes produce the same translation, the modes have not been benchmarked against each other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(AsRefStr, Display, EnumString, VariantArray, Serialize, Deserialize)]
+pub enum ParallelismMode {
+    /// Translates the topics in parallel, the words of a topic sequentially.
+    TopicLevel,
+    /// Translates the topics sequentially, the words and candidates of a topic in parallel.
+    VoterLevel,
+    /// Translates the topics as well as the words and candidates of a topic in parallel.
+    #[default]
+    Both,
+}
+
+impl ParallelismMode {
+    /// The minimal length of a rayon split over the topics. Splitting is disabled if
+    /// the topics are not processed in parallel.
+    pub fn topic_min_len(&self) -> usize {
+        match self {
+            ParallelismMode::VoterLevel => usize::MAX,
+            ParallelismMode::TopicLevel | ParallelismMode::Both => 1
+        }
+    }
+
+    /// The minimal length of a rayon split over the words and candidates of a topic.
+    /// Splitting is disabled if they are not processed in parallel.
+    pub fn voter_min_len(&self) -> usize {
+        match self {
+            ParallelismMode::TopicLevel => usize::MAX,
+            ParallelismMode::VoterLevel | ParallelismMode::Both => 1
+        }
+    }
+}
+
+/// The normalization of the candidate scores of a topic after the voting.
+///
+/// The translated topics are always normalized to sum up to one after the missing words are
+/// filled with the epsilon. Hence [ScoreNormalization::SumToOne] only changes the weight of the
+/// epsilon relative to the candidates, while [ScoreNormalization::Softmax] also changes the
+/// ratios between the candidates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(AsRefStr, Display, EnumString, VariantArray, Serialize, Deserialize)]
+pub enum ScoreNormalization {
+    /// Keeps the scores of the voting.
+    #[default]
+    None,
+    /// Divides the scores by their sum.
+    SumToOne,
+    /// Applies the softmax function to the scores.
+    Softmax,
+}
+
+impl ScoreNormalization {
+    /// Normalizes the `scores` in place. Does nothing if the scores can not be normalized.
+    pub fn normalize<'a>(&self, scores: impl IntoIterator<Item=&'a mut f64>) {
+        match self {
+            ScoreNormalization::None => {}
+            ScoreNormalization::SumToOne => {
+                let mut scores = scores.into_iter().collect_vec();
+                let sum = scores.iter().map(|value| **value).sum::<f64>();
+                if sum.is_normal() {
+                    for value in scores.iter_mut() {
+                        **value /= sum;
+                    }
+                }
+            }
+            ScoreNormalization::Softmax => {
+                let mut scores = scores.into_iter().collect_vec();
+                let max = scores.iter().map(|value| **value).fold(f64::NEG_INFINITY, f64::max);
+                if !max.is_finite() {
+                    return;
+                }
+                for value in scores.iter_mut() {
+                    **value = (**value - max).exp();
+                }
+                let sum = scores.iter().map(|value| **value).sum::<f64>();
+                for value in scores.iter_mut() {
+                    **value /= sum;
+                }
+            }
+        }
+    }
+}
+
 
 /// Setting if to keep the original word from language A
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Hash, Default)]
-#[derive(AsRefStr, Display, EnumString)]
+#[derive(AsRefStr, Display, EnumString, Serialize, Deserialize)]
 #[pyclass]
 pub enum KeepOriginalWord {
     Always,
@@ -129,7 +539,14 @@ pub enum TranslateError<'a> {
         lang_model: &'a LanguageHint,
     },
     #[error(transparent)]
-    AsVariableProviderFailed(#[from] AsVariableProviderError)
+    AsVariableProviderFailed(#[from] AsVariableProviderError),
+    #[error("The voting requires the voter field {0}, but it is not provided!")]
+    MissingVoterField(String),
+    #[error("Only {matched} of {total} words of the topic model are in the dictionary!")]
+    InsufficientOverlap {
+        matched: usize,
+        total: usize
+    }
 }
 
 #[derive(Debug, Error)]
@@ -218,7 +635,7 @@ pub fn translate_topic_model_without_provider<'a, Model, D, T, Voc, V>(
     T: Hash + Eq + Ord + Clone,
     V: VotingMethodMarker,
     Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
-    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc>,
+    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc> + ToTopicModelSpecific<T, Voc>,
     Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
 {
     translate_topic_model(
@@ -239,10 +656,86 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
     T: Hash + Eq + Ord + Clone,
     V: VotingMethodMarker,
     Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
-    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc>,
+    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc> + ToTopicModelSpecific<T, Voc>,
     Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
     P: AsVariableProvider<T>
 {
+    translate_topic_model_with_untranslated(
+        topic_model,
+        dictionary,
+        translate_config,
+        provider
+    ).map(|(translated, _)| translated)
+}
+
+/// Translates the topic model and returns the words of language A without any translation candidate
+/// in all topics, if [TranslateConfig::with_untranslated_report] is enabled.
+pub fn translate_topic_model_with_untranslated<'a, Model, D, T, Voc, V, P>(
+    topic_model: &'a Model,
+    dictionary: &'a D,
+    translate_config: &TranslateConfig<V>,
+    provider: Option<&P>
+) -> Result<(TopicModel<T, Vocabulary<T>>, Option<Vec<HashRef<T>>>), TranslateError<'a>> where
+    T: Hash + Eq + Ord + Clone,
+    V: VotingMethodMarker,
+    Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
+    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc> + ToTopicModelSpecific<T, Voc>,
+    Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
+    P: AsVariableProvider<T>
+{
+    translate_topic_model_with_diagnostics(
+        topic_model,
+        dictionary,
+        translate_config,
+        provider
+    ).map(|(translated, diagnostics)| (translated, diagnostics.untranslated))
+}
+
+/// Diagnostics of a translation, only the parts enabled in the [TranslateConfig] are set.
+#[derive(Debug, Clone)]
+pub struct TranslationDiagnostics<T, D> {
+    /// The words of language A without any candidate in all topics,
+    /// see [TranslateConfig::with_untranslated_report].
+    pub untranslated: Option<Vec<HashRef<T>>>,
+    /// The topic model specific dictionary used for the translation,
+    /// see [TranslateConfig::with_topic_specific_dictionary].
+    pub topic_specific_dictionary: Option<D>,
+}
+
+impl<T, D> std::fmt::Display for TranslationDiagnostics<T, D> where T: std::fmt::Display, D: BasicDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref dictionary) = self.topic_specific_dictionary {
+            let words_a = dictionary.map_a_to_b().iter().filter(|translations| !translations.is_empty()).count();
+            let words_b = dictionary.map_b_to_a().iter().filter(|translations| !translations.is_empty()).count();
+            let translations = dictionary.map_a_to_b().iter().map(|translations| translations.len()).sum::<usize>();
+            writeln!(f, "Topic specific dictionary: {translations} translations between {words_a} words in A and {words_b} words in B")?;
+        }
+        if let Some(ref untranslated) = self.untranslated {
+            write!(f, "Untranslated ({}):", untranslated.len())?;
+            for word in untranslated {
+                write!(f, " {word}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Translates the topic model and returns the [TranslationDiagnostics] enabled in `translate_config`.
+pub fn translate_topic_model_with_diagnostics<'a, Model, D, T, Voc, V, P>(
+    topic_model: &'a Model,
+    dictionary: &'a D,
+    translate_config: &TranslateConfig<V>,
+    provider: Option<&P>
+) -> Result<(TopicModel<T, Vocabulary<T>>, TranslationDiagnostics<T, D>), TranslateError<'a>> where
+    T: Hash + Eq + Ord + Clone,
+    V: VotingMethodMarker,
+    Voc: VocabularyMut<T> + MappableVocabulary<T> + Clone + 'a,
+    D: DictionaryWithVocabulary<T, Voc> + DictionaryMut<T, Voc> + FromVoc<T, Voc> + ToTopicModelSpecific<T, Voc>,
+    Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
+    P: AsVariableProvider<T>
+{
+    let memory_reporter = MemoryReporter::new();
 
     if let Some(lang_model) = topic_model.vocabulary().language() {
         if let (Some(lang_a), lang_b) = dictionary.language_direction() {
@@ -259,10 +752,21 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
         }
     }
 
-    let dictionary: D = create_topic_model_specific_dictionary::<D, D, T, Voc, Voc>(
-        dictionary,
-        topic_model.vocabulary()
-    );
+    let total = topic_model.vocabulary().len();
+    if total > 0 {
+        let matched = topic_model
+            .vocabulary()
+            .iter()
+            .filter(|word| dictionary.voc_a().contains::<T>(word))
+            .count();
+        let min_vocabulary_overlap = translate_config.min_vocabulary_overlap.unwrap_or(0.0);
+        if matched == 0 || (matched as f64 / total as f64) < min_vocabulary_overlap {
+            return Err(TranslateError::InsufficientOverlap { matched, total })
+        }
+    }
+
+    let dictionary: D = dictionary.to_topic_model_specific(topic_model.vocabulary());
+    memory_reporter.log_phase("dictionary_creation");
 
     // TODO: make clean for rust.
     let provider = if let Some(provider) = provider {
@@ -271,6 +775,22 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
         None
     }.transpose()?;
 
+    // The voters get the same variables as the sample, unless a provider only provides variables for some of them.
+    if let Some(sample) = topic_model.get_word_meta(0, 0) {
+        let mut sample_context = create_voter_context(sample, translate_config.voter_weight(sample.word_id), translate_config.rank_smoothing);
+        if let Some(ref provider) = provider {
+            provider.provide_for_word_a(sample.word_id, &mut sample_context)?;
+            provider.provide_for_word_in_topic_a(0, sample.word_id, &mut sample_context)?;
+        }
+        if let Some(missing) = translate_config
+            .voting
+            .required_voter_fields()
+            .into_iter()
+            .find(|field| sample_context.get_value(field).is_none()) {
+            return Err(TranslateError::MissingVoterField(missing.to_string()))
+        }
+    }
+
     let epsilon = if let Some(value) = translate_config.epsilon {
         value
     } else {
@@ -302,6 +822,7 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
         .par_iter()
         .zip_eq(topic_model.topic_metas())
         .enumerate()
+        .with_min_len(translate_config.parallelism.topic_min_len())
         .map(|(topic_id, (topic, meta))| {
             let mut topic_context_2 = context_map! {
                 TOPIC_MAX_PROBABILITY => meta.stats.max_value,
@@ -311,8 +832,11 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
                 TOPIC_ID => topic_id as i64
             }.unwrap();
 
-            if let Some(provider) = provider.as_ref() {
-                match provider.provide_for_topic(topic_id, &mut topic_context_2) {
+            let translated = if let Some(provider) = provider.as_ref() {
+                // The topic is translated to the topic with the same id.
+                match provider.provide_for_topic(topic_id, &mut topic_context_2).and_then(|_| {
+                    provider.provide_for_topic_pair(topic_id, topic_id, &mut topic_context_2)
+                }) {
                     Ok(_) => {
                         let topic_context_2 = topic_context_2
                             .to_static_with(topic_context.clone());
@@ -344,11 +868,30 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
                     &translate_config,
                     None::<&VariableProvider>
                 ).map_err(TranslateError::WithOrigin)
-            }
-
-
+            };
+            memory_reporter.log_phase(format_args!("topic_translation_{topic_id}"));
+            translated
     }).collect::<Result<Vec<_>, _>>()?;
 
+    let untranslated = if translate_config.collect_untranslated {
+        let mut has_candidate = vec![false; topic_model.vocabulary().len()];
+        // A kept original word is no translation.
+        for candidate in result.iter().flatten() {
+            if let Target(_) = candidate.candidate_word_id {
+                has_candidate[candidate.origin_word_id] = true;
+            }
+        }
+        Some(
+            has_candidate
+                .into_iter()
+                .enumerate()
+                .filter(|(_, has_candidate)| !has_candidate)
+                .map(|(word_id, _)| topic_model.vocabulary().get_value(word_id).unwrap().clone())
+                .collect_vec()
+        )
+    } else {
+        None
+    };
 
     let voc_b_col = result.par_iter().flatten().map(|value| {
         match value.candidate_word_id {
@@ -364,6 +907,7 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
 
     let mut voc_b = voc_b_col.iter().flatten().cloned().collect::<Vocabulary<_>>();
     voc_b.set_language(dictionary.language::<B>().cloned());
+    memory_reporter.log_phase("vocabulary_building");
 
     let mut counts = vec![0u64; voc_b.len()];
 
@@ -386,6 +930,9 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
             (voc_b.get_id(word).unwrap(), candidate.relative_score)
         }).collect::<HashMap<_, _>>();
 
+        // The candidates are normalized before the missing words are filled with epsilon.
+        translate_config.score_normalization.normalize(topic.values_mut());
+
         voc_b.ids().for_each(|value| {
             match topic.entry(value) {
                 Entry::Vacant(entry) => {
@@ -408,14 +955,19 @@ pub(crate) fn translate_topic_model<'a, Model, D, T, Voc, V, P>(
 
     translated.normalize_in_place();
 
-    return Ok(translated)
+    let diagnostics = TranslationDiagnostics {
+        untranslated,
+        topic_specific_dictionary: translate_config.keep_topic_specific_dictionary.then_some(dictionary)
+    };
+
+    return Ok((translated, diagnostics))
 }
 
 #[derive(Debug, Clone)]
 struct Candidate {
     candidate_word_id: LanguageOrigin<usize>,
     relative_score: f64,
-    _origin_word_id: usize
+    origin_word_id: usize
 }
 
 
@@ -423,12 +975,12 @@ impl Candidate {
     pub fn new(
         candidate_word_id: LanguageOrigin<usize>,
         relative_score: f64,
-        _origin_word_id: usize,
+        origin_word_id: usize,
     ) -> Self {
         Self {
             candidate_word_id,
             relative_score,
-            _origin_word_id
+            origin_word_id
         }
     }
 }
@@ -464,6 +1016,7 @@ fn translate_topic<Model, T, V, Voc, P>(
     provider: Option<&P>
 ) -> Result<Vec<Candidate>, TranslateErrorWithOrigin>
     where V: VotingMethodMarker,
+          T: Eq,
           Voc: BasicVocabulary<T>,
           Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
           P: VariableProviderOut
@@ -471,6 +1024,7 @@ fn translate_topic<Model, T, V, Voc, P>(
     topic
         .par_iter()
         .enumerate()
+        .with_min_len(config.parallelism.voter_min_len())
         .filter_map(|(original_word_id, probability)| {
 
             if let Some(provider) = provider {
@@ -521,6 +1075,66 @@ fn translate_topic<Model, T, V, Voc, P>(
     })
 }
 
+/// Creates the context of a voter with the reserved voter variables.
+fn create_voter_context(meta: &WordMeta, weight: f64, rank_smoothing: f64) -> HashMapContext {
+    context_map! {
+        RECIPROCAL_RANK => 1./ (meta.importance_rank() as f64 + rank_smoothing),
+        REAL_RECIPROCAL_RANK => 1./ (meta.rank() as f64 + rank_smoothing),
+        RANK => meta.rank() as i64,
+        IMPORTANCE => meta.importance_rank() as i64,
+        SCORE => meta.probability,
+        WEIGHT => weight,
+        VOTER_ID => meta.word_id as i64
+    }.unwrap()
+}
+
+/// The contexts of all voters of the candidates for a single word in language A.
+/// Each voter context is built once and cloned for every candidate, the shared
+/// contexts are never handed out mutably.
+struct VoterContextCache {
+    contexts: HashMap<usize, HashMapContext>
+}
+
+impl VoterContextCache {
+    /// Builds the contexts for the distinct voters in `voters`.
+    fn build<'a, V, P>(
+        voters: impl Iterator<Item=&'a Arc<WordMeta>>,
+        topic_id: usize,
+        config: &TranslateConfig<V>,
+        provider: Option<&P>
+    ) -> Result<Self, VariableProviderError>
+        where V: VotingMethodMarker,
+              P: VariableProviderOut
+    {
+        let mut contexts = HashMap::new();
+        for voter in voters {
+            if let Entry::Vacant(entry) = contexts.entry(voter.word_id) {
+                let mut context = create_voter_context(voter, config.voter_weight(voter.word_id), config.rank_smoothing);
+                if let Some(provider) = provider {
+                    provider.provide_for_word_a(voter.word_id, &mut context)?;
+                    provider.provide_for_word_in_topic_a(topic_id, voter.word_id, &mut context)?;
+                }
+                entry.insert(context);
+            }
+        }
+        Ok(Self { contexts })
+    }
+
+    /// The number of distinct voters.
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// Clones the contexts of `voters` in the given order.
+    fn voters_for(&self, voters: &[&Arc<WordMeta>]) -> Vec<HashMapContext> {
+        voters
+            .iter()
+            .map(|voter| self.contexts.get(&voter.word_id).expect("Every voter has to be cached!").clone())
+            .collect()
+    }
+}
+
 #[inline(always)]
 fn translate_single_candidate<Model, T, V, Voc, P>(
     topic_model: &Model,
@@ -533,29 +1147,53 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
     provider: Option<&P>
 ) -> Option<Result<Vec<Candidate>, TranslateErrorWithOrigin>>
     where V: VotingMethodMarker,
+          T: Eq,
           Voc: BasicVocabulary<T> ,
           Model: TopicModelWithVocabulary<T, Voc> + TopicModelWithDocumentStats,
           P: VariableProviderOut
 {
     let candidates = if let Some(candidates) = dictionary.translate_id_to_ids::<AToB>(original_word_id) {
-        Some(candidates.par_iter().cloned().filter_map( |candidate|
-            match dictionary.translate_id_to_ids::<BToA>(candidate) {
-                None  => None,
-                Some(voters) if voters.is_empty() => None,
-                Some(voters) => {
-                    let mapped = voters
-                        .iter()
-                        .filter_map(|word_id_a_retrans| {
-                            topic_model.get_word_meta(topic_id, *word_id_a_retrans)
-                        });
-
-                    let mapped = if let Some(threshold) = config.threshold {
-                        mapped.filter(|value| value.probability >= threshold).collect_vec()
-                    } else {
-                        mapped.collect_vec()
-                    };
-
+        let original_word = if config.exclude_self {
+            dictionary.id_to_word::<A>(original_word_id)
+        } else {
+            None
+        };
+        let candidates = candidates.iter().copied().filter(|candidate| {
+            original_word.map_or(true, |original_word| {
+                dictionary.id_to_word::<B>(*candidate).map_or(true, |word| word != original_word)
+            })
+        }).collect_vec();
+        let candidate_voters = candidates.into_par_iter().with_min_len(config.parallelism.voter_min_len()).filter_map(|candidate| {
+            let voters = dictionary.translate_id_to_ids::<BToA>(candidate)?;
+            if voters.is_empty() {
+                return None
+            }
+            let mapped = voters
+                .iter()
+                .filter_map(|word_id_a_retrans| {
+                    topic_model.get_word_meta(topic_id, *word_id_a_retrans)
+                });
+
+            let mut mapped = if let Some(threshold) = config.threshold {
+                mapped.filter(|value| value.probability >= threshold).collect_vec()
+            } else {
+                mapped.collect_vec()
+            };
+            if let Some(limit) = config.per_candidate_voter_limit {
+                mapped.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+                mapped.truncate(limit.get());
+            }
+            Some((candidate, mapped))
+        }).collect::<Vec<_>>();
 
+        match VoterContextCache::build(
+            candidate_voters.iter().flat_map(|(_, mapped)| mapped.iter().copied()),
+            topic_id,
+            config,
+            provider
+        ) {
+            Ok(cache) => {
+                Some(candidate_voters.into_par_iter().with_min_len(config.parallelism.voter_min_len()).map(|(candidate, mapped)| {
                     let mut context = context_map! {
                         COUNT_OF_VOTERS => mapped.len() as i64,
                         HAS_TRANSLATION => true,
@@ -564,72 +1202,45 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
                         CANDIDATE_ID => candidate as i64
                     }.unwrap();
 
+                    if let Some(provider) = provider {
+                        provider.provide_for_word_b(candidate, &mut context).and_then(|_| {
+                            provider.provide_for_word_in_topic_b(topic_id, candidate, &mut context)
+                        }).map_err(|err| TranslateErrorWithOrigin {
+                            topic_id,
+                            word_id: original_word_id,
+                            source: err.into()
+                        })?;
+                    }
+
                     let mut context = context.combine_with_mut(topic_context);
 
-                    let voters = mapped
-                        .iter()
-                        .map(|value| {
-                            let mut m = context_map! {
-                                RECIPROCAL_RANK => 1./ value.importance_rank() as f64,
-                                REAL_RECIPROCAL_RANK => 1./ value.rank() as f64,
-                                RANK => value.rank() as i64,
-                                IMPORTANCE => value.importance_rank() as i64,
-                                SCORE => value.probability,
-                                VOTER_ID => value.word_id as i64
-                            }.unwrap();
-                            if let Some(provider) = provider {
-                                match provider.provide_for_word_a(value.word_id, &mut m) {
-                                    Ok(_) => {
-                                        match provider.provide_for_word_in_topic_a(topic_id, value.word_id, &mut m) {
-                                            Ok(_) => {
-                                                Ok(m)
-                                            }
-                                            Err(err) => {Err(err)}
-                                        }
-                                    }
-                                    Err(err) => {
-                                        Err(err)
-                                    }
-                                }
-                            } else {
-                                Ok(m)
-                            }
-                        })
-                        .collect::<Result<Vec<_>, _>>();
-
-                    Some(
-                        match voters {
-                            Ok(mut voters) => {
-                                context.set_value(NUMBER_OF_VOTERS.to_string(), (voters.len() as i64).into()).expect("This should not fail!");
-                                match config.voting.execute_to_f64(&mut context, voters.as_mut_slice()) {
-                                    Ok(result) => {
-                                        Ok(Candidate::new(Target(candidate), result, original_word_id))
-                                    }
-                                    Err(err) => {
-                                        Err(err.originates_at(topic_id, original_word_id))
-                                    }
-                                }
-                            }
-                            Err(err) => {
-                                Err(TranslateErrorWithOrigin {
-                                    topic_id,
-                                    word_id: original_word_id,
-                                    source: err.into()
-                                })
-                            }
+                    let mut voters = cache.voters_for(&mapped);
+                    context.set_value(NUMBER_OF_VOTERS.to_string(), (voters.len() as i64).into()).expect("This should not fail!");
+                    match config.voting.execute_to_f64(&mut context, voters.as_mut_slice()) {
+                        Ok(result) => {
+                            Ok(Candidate::new(Target(candidate), result, original_word_id))
                         }
-
-                    )
-                }
+                        Err(err) => {
+                            Err(err.originates_at(topic_id, original_word_id))
+                        }
+                    }
+                }).collect::<Result<Vec<Candidate>, TranslateErrorWithOrigin>>())
             }
-        ).collect::<Result<Vec<Candidate>, TranslateErrorWithOrigin>>())
+            Err(err) => {
+                Some(Err(TranslateErrorWithOrigin {
+                    topic_id,
+                    word_id: original_word_id,
+                    source: err.into()
+                }))
+            }
+        }
     } else {
         // Unknown
         None
     };
 
 
-    fn vote_for_origin<'a>(topic_model: &'a impl BasicTopicModel, topic_context: &(impl Context + Send + Sync + IterateVariablesContext), has_translation: bool, topic_id: usize, word_id: usize, probability: f64, voting: &(impl VotingMethod + Sync + Send)) -> Result<Candidate, TranslateErrorWithOrigin> {
+    fn vote_for_origin<'a>(topic_model: &'a impl BasicTopicModel, topic_context: &(impl Context + Send + Sync + IterateVariablesContext), has_translation: bool, topic_id: usize, word_id: usize, probability: f64, weight: f64, rank_smoothing: f64, voting: &(impl VotingMethod + Sync + Send)) -> Result<Candidate, TranslateErrorWithOrigin> {
         let mut context = context_map! {
             COUNT_OF_VOTERS => 1,
             HAS_TRANSLATION => has_translation,
@@ -644,14 +1255,7 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
         let original_meta = topic_model.get_word_meta(topic_id, word_id).unwrap();
 
         let mut voters = vec![
-            context_map! {
-                RECIPROCAL_RANK => 1./ original_meta.importance_rank() as f64,
-                REAL_RECIPROCAL_RANK => 1./ original_meta.rank() as f64,
-                RANK => original_meta.rank() as i64,
-                IMPORTANCE => original_meta.importance_rank() as i64,
-                SCORE => original_meta.probability,
-                VOTER_ID => word_id as i64
-            }.unwrap()
+            create_voter_context(original_meta, weight, rank_smoothing)
         ];
 
         match voting.execute_to_f64(&mut context, voters.as_mut_slice()) {
@@ -674,6 +1278,8 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
                     topic_id,
                     original_word_id,
                     probability,
+                    config.voter_weight(original_word_id),
+                    config.rank_smoothing,
                     &config.voting
                 ) {
                     Ok(value) => {
@@ -690,6 +1296,8 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
                     topic_id,
                     original_word_id,
                     probability,
+                    config.voter_weight(original_word_id),
+                    config.rank_smoothing,
                     &config.voting
                 ) {
                     Ok(value) => {
@@ -711,6 +1319,8 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
                         topic_id,
                         original_word_id,
                         probability,
+                        config.voter_weight(original_word_id),
+                        config.rank_smoothing,
                         &config.voting
                     ) {
                         Ok(value) => {
@@ -756,16 +1366,28 @@ fn translate_single_candidate<Model, T, V, Voc, P>(
 
 #[cfg(test)]
 pub(crate) mod test {
+    use std::collections::HashSet;
     use std::num::NonZeroUsize;
-    use crate::topicmodel::dictionary::{Dictionary, DictionaryMut};
-    use crate::topicmodel::dictionary::direction::Invariant;
-    use crate::topicmodel::topic_model::{TopicModel};
-    use crate::topicmodel::vocabulary::{SearchableVocabulary, Vocabulary};
+    use crate::topicmodel::dictionary::{BasicDictionary, BasicDictionaryWithVocabulary, Dictionary, DictionaryMut};
+    use crate::topicmodel::dictionary::direction::{AToB, BToA, Invariant};
+    use crate::topicmodel::topic_model::{BasicTopicModel, BasicTopicModelWithVocabulary, TopicModel, TopicModelWithVocabulary, WordImportanceAggregation};
+    use crate::topicmodel::enums::EnsembleError;
+    use crate::topicmodel::vocabulary::{BasicVocabulary, SearchableVocabulary, Vocabulary};
     use crate::translate::KeepOriginalWord::Never;
-    use crate::translate::{translate_topic_model_without_provider, TranslateConfig};
+    use crate::translate::{translate_topic_model_without_provider, KeepOriginalWord, TranslateError, ParallelismMode, Preset, ScoreNormalization, TranslateConfig, TranslateConfigBuilder, TranslateConfigError};
+    use strum::VariantArray;
+    use itertools::Itertools;
     use crate::voting::spy::{IntoSpy};
     use Extend;
     use crate::voting::BuildInVoting;
+    use evalexpr::{Context, ContextWithMutableVariables, HashMapContext, Value};
+    use crate::external_variable_provider::{EmbeddingBoostProvider, TopicSimilarityProvider, VariableProvider};
+    use crate::translate::{create_voter_context, translate_single_candidate, translate_topic_model, translate_topic_model_with_diagnostics, translate_topic_model_with_untranslated, DummyAsVariableProvider, LanguageOrigin, VoterContextCache};
+    use crate::topicmodel::topic_model::meta::WordMeta;
+    use crate::variable_names::{EMBEDDING_SIMILARITY_A, RECIPROCAL_RANK, SCORE, TOPIC_ID, TOPIC_SIMILARITY_SCORE, WEIGHT};
+    use crate::voting::parser::logic::global_voting_function;
+    use crate::voting::{VotingMethod, VotingMethodContext, VotingResult};
+    use crate::voting::traits::VotingMethodMarker;
 
     pub fn create_test_data() -> (Vocabulary<String>, Vocabulary<String>, Dictionary<String, Vocabulary<String>>){
         let mut voc_a = Vocabulary::<String>::default();
@@ -832,17 +1454,254 @@ pub(crate) mod test {
         (voc_a, voc_b, dict)
     }
 
+    /// A topic model with two topics over the vocabulary `voc_a` of [create_test_data].
+    pub fn create_test_model(voc_a: Vocabulary<String>) -> TopicModel<String, Vocabulary<String>> {
+        TopicModel::new(
+            vec![
+                vec![0.019, 0.018, 0.012, 0.009, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008],
+                vec![0.002, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.02, 0.0001],
+            ],
+            voc_a,
+            vec![10, 5, 8, 1, 2, 3, 1, 1, 1, 1, 2],
+            vec![
+                vec![0.7, 0.2],
+                vec![0.8, 0.3]
+            ],
+            vec![
+                200,
+                300
+            ]
+        )
+    }
+
     #[test]
     fn test_complete_translation(){
         let (voc_a, _, dict) = create_test_data();
 
+        let model_a = create_test_model(voc_a);
+
+        let config = TranslateConfig::builder(BuildInVoting::PCombSum.spy())
+            .keep_original_word(Never)
+            .top_candidate_limit(Some(3))
+            .build()
+            .unwrap();
+
+        let model_b = translate_topic_model_without_provider(
+            &model_a,
+            &dict,
+            &config,
+        ).unwrap();
+
+        model_b.show_10().unwrap();
+    }
+
+    #[test]
+    fn test_global_word_importance(){
+        let (voc_a, _, dict) = create_test_data();
+
+        let model_a = create_test_model(voc_a);
+
+        let config = TranslateConfig::builder(BuildInVoting::PCombSum)
+            .keep_original_word(Never)
+            .top_candidate_limit(Some(3))
+            .build()
+            .unwrap();
+
+        let model_b = translate_topic_model_without_provider(
+            &model_a,
+            &dict,
+            &config,
+        ).unwrap();
+
+        for aggregation in [WordImportanceAggregation::Sum, WordImportanceAggregation::Max] {
+            let importance = model_b.global_word_importance(aggregation);
+            assert_eq!(model_b.vocabulary().len(), importance.len());
+            assert!(importance.windows(2).all(|value| value[0].1 >= value[1].1));
+            let top = importance.iter().take(4).map(|(word, _)| word.as_str()).collect::<Vec<_>>();
+            assert!(top.contains(&"Tragfläche"), "Tragfläche not in {top:?}");
+            assert!(top.contains(&"Flugzeug"), "Flugzeug not in {top:?}");
+        }
+    }
+
+    /// Sums up the weighted scores of all voters.
+    struct WeightedSum;
+
+    impl VotingMethod for WeightedSum {
+        fn execute<A, B>(&self, _: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+            let mut sum = 0.0;
+            for voter in voters.iter() {
+                sum += voter.get_value(SCORE).unwrap().as_number()? * voter.get_value(WEIGHT).unwrap().as_number()?;
+            }
+            Ok(sum.into())
+        }
+    }
+
+    impl VotingMethodMarker for WeightedSum {}
+
+    #[test]
+    fn voter_weights_change_the_winner(){
+        let mut voc_a = Vocabulary::<String>::default();
+        voc_a.extend(vec!["a1".to_string(), "a2".to_string(), "a3".to_string()]);
+
+        let mut dict = Dictionary::new();
+        dict.insert_value::<Invariant>("a1".to_string(), "b1".to_string());
+        dict.insert_value::<Invariant>("a1".to_string(), "b2".to_string());
+        dict.insert_value::<Invariant>("a2".to_string(), "b1".to_string());
+        dict.insert_value::<Invariant>("a3".to_string(), "b2".to_string());
+
         let model_a = TopicModel::new(
             vec![
-                vec![0.019, 0.018, 0.012, 0.009, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008],
-                vec![0.002, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.02, 0.0001],
+                vec![0.5, 0.3, 0.2],
             ],
             voc_a,
-            vec![10, 5, 8, 1, 2, 3, 1, 1, 1, 1, 2],
+            vec![1, 1, 1],
+            vec![
+                vec![1.0]
+            ],
+            vec![
+                3
+            ]
+        );
+
+        fn winner(model: &TopicModel<String, Vocabulary<String>>, dict: &Dictionary<String, Vocabulary<String>>, config: &TranslateConfig<WeightedSum>) -> usize {
+            let mut candidates = translate_single_candidate::<_, String, _, _, VariableProvider>(
+                model,
+                dict,
+                0,
+                &HashMapContext::new(),
+                config,
+                0,
+                0.5,
+                None
+            ).unwrap().unwrap();
+            candidates.sort();
+            match candidates.first().unwrap().candidate_word_id {
+                LanguageOrigin::Target(word_id) => word_id,
+                LanguageOrigin::Origin(_) => unreachable!()
+            }
+        }
+
+        let b1 = dict.voc_b().get_id("b1").unwrap();
+        let b2 = dict.voc_b().get_id("b2").unwrap();
+
+        let config = TranslateConfig::new(WeightedSum, None, None, Never, None);
+        assert_eq!(b1, winner(&model_a, &dict, &config));
+
+        let config = config.with_voter_weights(vec![1.0, 1.0, 3.0]);
+        assert_eq!(b2, winner(&model_a, &dict, &config));
+    }
+
+    #[test]
+    fn per_candidate_voter_limit_keeps_the_best_voters(){
+        let mut voc_a = Vocabulary::<String>::default();
+        voc_a.extend(vec!["a1".to_string(), "a2".to_string(), "a3".to_string()]);
+
+        let mut dict = Dictionary::new();
+        dict.insert_value::<Invariant>("a1".to_string(), "b1".to_string());
+        dict.insert_value::<Invariant>("a2".to_string(), "b1".to_string());
+        dict.insert_value::<Invariant>("a3".to_string(), "b1".to_string());
+
+        let model_a = TopicModel::new(
+            vec![
+                vec![0.2, 0.5, 0.3],
+            ],
+            voc_a,
+            vec![1, 1, 1],
+            vec![
+                vec![1.0]
+            ],
+            vec![
+                3
+            ]
+        );
+
+        fn score_of_b1(model: &TopicModel<String, Vocabulary<String>>, dict: &Dictionary<String, Vocabulary<String>>, config: &TranslateConfig<BuildInVoting>) -> f64 {
+            let candidates = translate_single_candidate::<_, String, _, _, VariableProvider>(
+                model,
+                dict,
+                0,
+                &HashMapContext::new(),
+                config,
+                0,
+                0.2,
+                None
+            ).unwrap().unwrap();
+            assert_eq!(1, candidates.len());
+            candidates[0].relative_score
+        }
+
+        let config = TranslateConfig::builder(BuildInVoting::CombSum).build().unwrap();
+        assert!((score_of_b1(&model_a, &dict, &config) - 1.0).abs() < 1E-10);
+
+        let config = TranslateConfig::builder(BuildInVoting::CombSum).per_candidate_voter_limit(Some(1)).build().unwrap();
+        assert!((score_of_b1(&model_a, &dict, &config) - 0.5).abs() < 1E-10);
+
+        let config = TranslateConfig::builder(BuildInVoting::CombSum).per_candidate_voter_limit(Some(2)).build().unwrap();
+        assert!((score_of_b1(&model_a, &dict, &config) - 0.8).abs() < 1E-10);
+    }
+
+    #[test]
+    fn exclude_self_skips_self_translations(){
+        let mut voc_a = Vocabulary::<String>::default();
+        voc_a.extend(vec!["internet".to_string(), "net".to_string()]);
+
+        let mut dict = Dictionary::new();
+        dict.insert_value::<Invariant>("internet".to_string(), "internet".to_string());
+        dict.insert_value::<Invariant>("internet".to_string(), "Netz".to_string());
+        dict.insert_value::<Invariant>("net".to_string(), "Netz".to_string());
+
+        let model_a = TopicModel::new(
+            vec![
+                vec![0.6, 0.4],
+            ],
+            voc_a,
+            vec![1, 1],
+            vec![
+                vec![1.0]
+            ],
+            vec![
+                2
+            ]
+        );
+
+        fn candidates_of_internet(model: &TopicModel<String, Vocabulary<String>>, dict: &Dictionary<String, Vocabulary<String>>, config: &TranslateConfig<BuildInVoting>) -> Vec<String> {
+            let candidates = translate_single_candidate::<_, String, _, _, VariableProvider>(
+                model,
+                dict,
+                0,
+                &HashMapContext::new(),
+                config,
+                dict.voc_a().get_id("internet").unwrap(),
+                0.6,
+                None
+            ).unwrap().unwrap();
+            candidates.into_iter().map(|candidate| {
+                match candidate.candidate_word_id {
+                    LanguageOrigin::Target(word_id) => dict.voc_b().get_value(word_id).unwrap().to_string(),
+                    LanguageOrigin::Origin(_) => unreachable!()
+                }
+            }).sorted().collect()
+        }
+
+        let config = TranslateConfig::builder(BuildInVoting::CombSum).build().unwrap();
+        assert_eq!(vec!["Netz".to_string(), "internet".to_string()], candidates_of_internet(&model_a, &dict, &config));
+
+        let config = config.with_exclude_self(true);
+        assert_eq!(vec!["Netz".to_string()], candidates_of_internet(&model_a, &dict, &config));
+    }
+
+    #[test]
+    fn can_report_untranslated_words(){
+        let (mut voc_a, _, dict) = create_test_data();
+        voc_a.extend(vec!["helicopter".to_string()]);
+
+        let model_a = TopicModel::new(
+            vec![
+                vec![0.019, 0.018, 0.012, 0.009, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.02],
+                vec![0.002, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.0001, 0.02, 0.0001, 0.01],
+            ],
+            voc_a,
+            vec![10, 5, 8, 1, 2, 3, 1, 1, 1, 1, 2, 4],
             vec![
                 vec![0.7, 0.2],
                 vec![0.8, 0.3]
@@ -853,21 +1712,512 @@ pub(crate) mod test {
             ]
         );
 
-        let config = TranslateConfig {
-            threshold: None,
-            voting: BuildInVoting::PCombSum.spy(),
-            epsilon: None,
-            keep_original_word: Never,
-            top_candidate_limit: Some(NonZeroUsize::new(3).unwrap())
-        };
+        let config = TranslateConfig::new(BuildInVoting::PCombSum, None, None, Never, None);
+        let (_, untranslated) = translate_topic_model_with_untranslated(
+            &model_a,
+            &dict,
+            &config,
+            None::<&DummyAsVariableProvider<String>>
+        ).unwrap();
+        assert!(untranslated.is_none());
 
-        let model_b = translate_topic_model_without_provider(
+        let config = config.with_untranslated_report(true);
+        let (model_b, untranslated) = translate_topic_model_with_untranslated(
             &model_a,
             &dict,
             &config,
+            None::<&DummyAsVariableProvider<String>>
         ).unwrap();
+        let untranslated = untranslated.unwrap();
+        assert_eq!(1, untranslated.len());
+        assert_eq!("helicopter", untranslated[0].as_str());
+        assert!(model_b.vocabulary().get_id("helicopter").is_none());
+
+        for keep_original_word in [KeepOriginalWord::IfNoTranslation, KeepOriginalWord::Always] {
+            let config = TranslateConfig::new(BuildInVoting::PCombSum, None, None, keep_original_word, None)
+                .with_untranslated_report(true);
+            let (model_b, untranslated) = translate_topic_model_with_untranslated(
+                &model_a,
+                &dict,
+                &config,
+                None::<&DummyAsVariableProvider<String>>
+            ).unwrap();
+            let untranslated = untranslated.unwrap();
+            assert_eq!(1, untranslated.len(), "{keep_original_word}");
+            assert_eq!("helicopter", untranslated[0].as_str(), "{keep_original_word}");
+            assert!(model_b.vocabulary().get_id("helicopter").is_some(), "{keep_original_word}");
+        }
+    }
 
-        model_b.show_10().unwrap();
+    #[test]
+    fn can_keep_the_topic_specific_dictionary(){
+        let (mut voc_a, _, dict) = create_test_data();
+        voc_a.extend(vec!["helicopter".to_string()]);
+
+        let model_a = TopicModel::new(
+            vec![
+                vec![0.019, 0.018, 0.012, 0.009, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.02],
+            ],
+            voc_a,
+            vec![10, 5, 8, 1, 2, 3, 1, 1, 1, 1, 2, 4],
+            vec![
+                vec![1.0],
+            ],
+            vec![
+                200,
+            ]
+        );
+
+        let config = TranslateConfig::new(BuildInVoting::PCombSum, None, None, Never, None);
+        let (_, diagnostics) = translate_topic_model_with_diagnostics(
+            &model_a,
+            &dict,
+            &config,
+            None::<&DummyAsVariableProvider<String>>
+        ).unwrap();
+        assert!(diagnostics.topic_specific_dictionary.is_none());
+        assert!(diagnostics.untranslated.is_none());
+
+        let config = config.with_topic_specific_dictionary(true).with_untranslated_report(true);
+        let (_, diagnostics) = translate_topic_model_with_diagnostics(
+            &model_a,
+            &dict,
+            &config,
+            None::<&DummyAsVariableProvider<String>>
+        ).unwrap();
+        let topic_specific = diagnostics.topic_specific_dictionary.as_ref().unwrap();
+        assert_eq!(model_a.vocabulary().len(), topic_specific.voc_a().len());
+        let plane = topic_specific.voc_a().get_id("plane").unwrap();
+        let translations = topic_specific
+            .translate_id_to_ids::<AToB>(plane)
+            .unwrap()
+            .iter()
+            .map(|word_id| topic_specific.voc_b().get_value(*word_id).unwrap().as_str())
+            .collect::<HashSet<_>>();
+        assert!(translations.contains("Flugzeug"));
+        let helicopter = topic_specific.voc_a().get_id("helicopter").unwrap();
+        assert!(topic_specific.translate_id_to_ids::<AToB>(helicopter).map_or(true, |value| value.is_empty()));
+
+        let summary = diagnostics.to_string();
+        assert!(summary.starts_with("Topic specific dictionary: "));
+        assert!(summary.contains("Untranslated (1): helicopter"));
+    }
+
+    #[test]
+    fn rejects_votings_with_missing_voter_fields(){
+        let (voc_a, voc_b, dict) = create_test_data();
+        let model_a = create_test_model(voc_a.clone());
+
+        let voting = global_voting_function::<nom::error::Error<_>>(
+            format!("aggregate(let w = sumOf): {SCORE} * {EMBEDDING_SIMILARITY_A}").as_str().into()
+        ).unwrap().1;
+        assert!(voting.required_voter_fields().contains(&EMBEDDING_SIMILARITY_A));
+        let config = TranslateConfig::new(voting, None, None, Never, None);
+
+        match translate_topic_model_without_provider(&model_a, &dict, &config) {
+            Err(TranslateError::MissingVoterField(field)) => assert_eq!(EMBEDDING_SIMILARITY_A, field),
+            other => panic!("Expected a missing voter field but got {other:?}")
+        }
+
+        let provider = EmbeddingBoostProvider::new(
+            voc_a.clone(),
+            (0..voc_a.len()).map(|word_id| vec![1.0, word_id as f32]).collect(),
+            voc_b.clone(),
+            (0..voc_b.len()).map(|word_id| vec![word_id as f32, 1.0]).collect(),
+        );
+        assert!(translate_topic_model(&model_a, &dict, &config, Some(&provider)).is_ok());
+    }
+
+    /// Records the topic similarity seen by each topic.
+    struct RecordTopicSimilarity(std::sync::Mutex<Vec<(i64, f64)>>);
+
+    impl VotingMethod for RecordTopicSimilarity {
+        fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+            let topic_id = global_context.get_value(TOPIC_ID).unwrap().as_int()?;
+            let similarity = global_context.get_value(TOPIC_SIMILARITY_SCORE).unwrap().as_number()?;
+            self.0.lock().unwrap().push((topic_id, similarity));
+            let mut sum = 0.0;
+            for voter in voters.iter() {
+                sum += voter.get_value(SCORE).unwrap().as_number()?;
+            }
+            Ok((sum * similarity).into())
+        }
+    }
+
+    impl VotingMethodMarker for RecordTopicSimilarity {}
+
+    #[test]
+    fn provides_the_topic_similarity_during_the_translation(){
+        let (voc_a, _, dict) = create_test_data();
+        let model_a = create_test_model(voc_a);
+
+        let provider = TopicSimilarityProvider::new(vec![
+            vec![0.5, 0.1],
+            vec![0.1, 0.25],
+        ]);
+        let config = TranslateConfig::new(RecordTopicSimilarity(Default::default()), None, None, Never, None);
+        translate_topic_model(&model_a, &dict, &config, Some(&provider)).unwrap();
+
+        let seen = config.voting.0.lock().unwrap();
+        assert!(!seen.is_empty());
+        for (topic_id, similarity) in seen.iter() {
+            assert_eq!(provider.similarity(*topic_id as usize, *topic_id as usize), Some(*similarity));
+        }
+        assert!(seen.iter().any(|(topic_id, _)| *topic_id == 0));
+        assert!(seen.iter().any(|(topic_id, _)| *topic_id == 1));
+    }
+
+    #[test]
+    fn can_translate_a_subset(){
+        let (voc_a, _, dict) = create_test_data();
+        let model_a = create_test_model(voc_a);
+
+        let config = TranslateConfig::builder(BuildInVoting::CombSum)
+            .epsilon(Some(1e-6))
+            .build()
+            .unwrap();
+
+        let full = translate_topic_model_without_provider(&model_a, &dict, &config).unwrap();
+        let subset: TopicModel<String, Vocabulary<String>> = model_a.select_topics(&[1]).unwrap().to_topic_model();
+        assert_eq!(1, subset.k());
+        let translated = translate_topic_model_without_provider(&subset, &dict, &config).unwrap();
+        assert_eq!(1, translated.k());
+
+        assert!(translated.vocabulary().len() > 0);
+        for (word_id, probability) in translated.get_topic(0).unwrap().iter().enumerate() {
+            let word = translated.vocabulary().get_value(word_id).unwrap();
+            let expected = full.get_probability_by_word(1, word.as_str()).copied().unwrap_or(0.0);
+            assert!((expected - probability).abs() < 1e-10, "{word}: {expected} != {probability}");
+        }
+    }
+
+    #[test]
+    fn rejects_dictionaries_without_overlap(){
+        let (mut voc_a, _, dict) = create_test_data();
+
+        let mut unknown = Vocabulary::<String>::default();
+        unknown.extend(vec!["helicopter".to_string(), "rotor".to_string()]);
+        let model_unknown = TopicModel::new(
+            vec![
+                vec![0.6, 0.4],
+            ],
+            unknown,
+            vec![1, 1],
+            vec![
+                vec![1.0],
+            ],
+            vec![
+                2,
+            ]
+        );
+        let config = TranslateConfig::new(BuildInVoting::PCombSum, None, None, Never, None);
+        assert!(matches!(
+            translate_topic_model_without_provider(&model_unknown, &dict, &config),
+            Err(TranslateError::InsufficientOverlap { matched: 0, total: 2 })
+        ));
+
+        voc_a.extend(vec!["helicopter".to_string()]);
+        let model_a = TopicModel::new(
+            vec![
+                vec![0.019, 0.018, 0.012, 0.009, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.008, 0.02],
+            ],
+            voc_a,
+            vec![10, 5, 8, 1, 2, 3, 1, 1, 1, 1, 2, 4],
+            vec![
+                vec![1.0],
+            ],
+            vec![
+                200,
+            ]
+        );
+        let config = config.with_min_vocabulary_overlap(Some(0.95));
+        assert!(matches!(
+            translate_topic_model_without_provider(&model_a, &dict, &config),
+            Err(TranslateError::InsufficientOverlap { matched: 11, total: 12 })
+        ));
+        let config = config.with_min_vocabulary_overlap(Some(0.9));
+        assert!(translate_topic_model_without_provider(&model_a, &dict, &config).is_ok());
+    }
+
+    #[test]
+    fn voter_context_cache_builds_each_voter_once(){
+        let (voc_a, _, dict) = create_test_data();
+        let model = create_test_model(voc_a);
+        let config = TranslateConfig::builder(BuildInVoting::CombSum).build().unwrap();
+
+        // "plane" has the highest fan-out in the fixture.
+        let plane = dict.voc_a().get_id("plane").unwrap();
+        let candidate_voters = dict
+            .translate_id_to_ids::<AToB>(plane)
+            .unwrap()
+            .iter()
+            .map(|candidate| {
+                dict.translate_id_to_ids::<BToA>(*candidate)
+                    .unwrap()
+                    .iter()
+                    .filter_map(|voter| model.get_word_meta(0, *voter))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let all_voters = candidate_voters.iter().flatten().count();
+        let distinct_voters = candidate_voters.iter().flatten().map(|voter| voter.word_id).collect::<HashSet<_>>().len();
+        assert!(all_voters > distinct_voters);
+
+        let cache = VoterContextCache::build(
+            candidate_voters.iter().flatten().copied(),
+            0,
+            &config,
+            None::<&VariableProvider>
+        ).unwrap();
+        for voters in candidate_voters.iter() {
+            let mut contexts = cache.voters_for(voters);
+            assert_eq!(voters.len(), contexts.len());
+            for context in contexts.iter_mut() {
+                context.set_value(SCORE.to_string(), Value::Float(-1.0)).unwrap();
+            }
+        }
+        assert_eq!(distinct_voters, cache.len());
+        // The shared contexts are not affected by changes to the cloned contexts.
+        for voters in candidate_voters.iter() {
+            for (voter, context) in voters.iter().zip(cache.voters_for(voters)) {
+                assert_eq!(Some(&Value::Float(voter.probability)), context.get_value(SCORE));
+            }
+        }
+    }
+
+    #[test]
+    fn rank_smoothing_flattens_reciprocal_ranks(){
+        fn reciprocal_ranks(rank_smoothing: f64) -> Vec<f64> {
+            (0..3).map(|position| {
+                let meta = WordMeta {
+                    topic_id: 0,
+                    word_id: position,
+                    probability: 0.1,
+                    position,
+                    importance: position
+                };
+                create_voter_context(&meta, 1.0, rank_smoothing)
+                    .get_value(RECIPROCAL_RANK)
+                    .unwrap()
+                    .as_number()
+                    .unwrap()
+            }).collect()
+        }
+
+        let unsmoothed = reciprocal_ranks(0.0);
+        assert_eq!(vec![1.0, 0.5, 1.0 / 3.0], unsmoothed);
+
+        let smoothed = reciprocal_ranks(60.0);
+        assert!(smoothed.windows(2).all(|value| value[0] > value[1]));
+        assert!(smoothed[0] / smoothed[2] < unsmoothed[0] / unsmoothed[2]);
+        assert!(smoothed[0] / smoothed[1] < 1.1);
+    }
+
+    #[test]
+    fn builder_validates_config(){
+        let config = TranslateConfig::builder(BuildInVoting::CombSum)
+            .epsilon(Some(0.1))
+            .threshold(Some(0.5))
+            .top_candidate_limit(Some(1))
+            .build()
+            .unwrap();
+        assert_eq!(Some(0.1), config.epsilon);
+        assert_eq!(NonZeroUsize::new(1), config.top_candidate_limit);
+        assert_eq!(Never, config.keep_original_word);
+
+        assert_eq!(
+            TranslateConfigError::InvalidEpsilon(0.0),
+            TranslateConfig::builder(BuildInVoting::CombSum).epsilon(Some(0.0)).build().unwrap_err()
+        );
+        assert_eq!(
+            TranslateConfigError::InvalidThreshold(1.5),
+            TranslateConfig::builder(BuildInVoting::CombSum).threshold(Some(1.5)).build().unwrap_err()
+        );
+        assert_eq!(
+            TranslateConfigError::InvalidTopCandidateLimit,
+            TranslateConfig::builder(BuildInVoting::CombSum).top_candidate_limit(Some(0)).build().unwrap_err()
+        );
+        assert_eq!(
+            TranslateConfigError::InvalidMinVocabularyOverlap(1.5),
+            TranslateConfig::builder(BuildInVoting::CombSum).min_vocabulary_overlap(Some(1.5)).build().unwrap_err()
+        );
+        assert_eq!(
+            TranslateConfigError::InvalidRankSmoothing(-1.0),
+            TranslateConfig::builder(BuildInVoting::CombSum).rank_smoothing(-1.0).build().unwrap_err()
+        );
+        assert!(matches!(
+            TranslateConfig::builder(BuildInVoting::CombSum).rank_smoothing(f64::NAN).build().unwrap_err(),
+            TranslateConfigError::InvalidRankSmoothing(value) if value.is_nan()
+        ));
+        assert_eq!(
+            TranslateConfigError::InvalidPerCandidateVoterLimit,
+            TranslateConfig::builder(BuildInVoting::CombSum).per_candidate_voter_limit(Some(0)).build().unwrap_err()
+        );
+        assert_eq!(
+            TranslateConfigError::MissingVoting,
+            TranslateConfigBuilder::<BuildInVoting>::new().build().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn can_serialize_and_reload_config(){
+        let config = TranslateConfig::builder(BuildInVoting::PCombSum)
+            .epsilon(Some(0.1))
+            .threshold(Some(0.005))
+            .keep_original_word(KeepOriginalWord::IfNoTranslation)
+            .top_candidate_limit(Some(3))
+            .voter_weights(Some(vec![1.0, 0.5, 2.0]))
+            .collect_untranslated(true)
+            .rank_smoothing(10.0)
+            .score_normalization(ScoreNormalization::SumToOne)
+            .per_candidate_voter_limit(Some(2))
+            .exclude_self(true)
+            .build()
+            .unwrap();
+        let serialized = serde_json::to_string(&config).unwrap();
+        let reloaded: TranslateConfig<BuildInVoting> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(format!("{config:?}"), format!("{reloaded:?}"));
+        assert_eq!(serialized, serde_json::to_string(&reloaded).unwrap());
+    }
+
+    #[test]
+    fn score_normalization_normalizes_per_topic(){
+        let mut scores = vec![1.0, 2.0, 5.0];
+        ScoreNormalization::SumToOne.normalize(scores.iter_mut());
+        assert_eq!(vec![0.125, 0.25, 0.625], scores);
+
+        let mut scores = vec![1.0, 1.0, 1.0, 1.0];
+        ScoreNormalization::Softmax.normalize(scores.iter_mut());
+        assert_eq!(vec![0.25; 4], scores);
+
+        let mut scores = vec![0.0, 0.0];
+        ScoreNormalization::SumToOne.normalize(scores.iter_mut());
+        assert_eq!(vec![0.0, 0.0], scores);
+
+        let (voc_a, _, dict) = create_test_data();
+        let model_a = create_test_model(voc_a);
+        let mut translated = Vec::new();
+        for normalization in ScoreNormalization::VARIANTS {
+            // The threshold leaves only a few candidates in the second topic, the missing words get the epsilon.
+            let config = TranslateConfig::builder(BuildInVoting::CombSum)
+                .epsilon(Some(0.001))
+                .threshold(Some(0.005))
+                .score_normalization(*normalization)
+                .build()
+                .unwrap();
+            let model_b = translate_topic_model_without_provider(
+                &model_a,
+                &dict,
+                &config,
+            ).unwrap_or_else(|err| panic!("{normalization} failed with {err}"));
+            for topic in model_b.topics() {
+                assert!((topic.iter().sum::<f64>() - 1.0).abs() < 1E-9, "{normalization} is not normalized");
+            }
+            translated.push((*normalization, model_b));
+        }
+
+        for ((normalization_a, model_a), (normalization_b, model_b)) in translated.iter().tuple_combinations() {
+            assert_eq!(
+                model_a.vocabulary().iter().collect_vec(),
+                model_b.vocabulary().iter().collect_vec()
+            );
+            let max_difference = model_a.topics().iter().flatten()
+                .zip_eq(model_b.topics().iter().flatten())
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            assert!(max_difference > 1E-6, "{normalization_a} and {normalization_b} have the same result");
+        }
+    }
+
+    #[test]
+    fn all_presets_can_translate(){
+        for preset in Preset::VARIANTS {
+            let (voc_a, _, dict) = create_test_data();
+
+            let model_a = create_test_model(voc_a);
+
+            let serialized = serde_json::to_string(preset).unwrap();
+            assert_eq!(*preset, serde_json::from_str::<Preset>(&serialized).unwrap());
+
+            let config = TranslateConfig::preset(*preset);
+            let model_b = translate_topic_model_without_provider(
+                &model_a,
+                &dict,
+                &config,
+            ).unwrap_or_else(|err| panic!("{preset} failed with {err}"));
+            assert!(model_b.vocabulary().len() > 0, "{preset} has an empty vocabulary");
+        }
+    }
+
+    #[test]
+    fn can_ensemble_translations(){
+        let (voc_a, _, dict) = create_test_data();
+
+        let model_a = create_test_model(voc_a);
+
+        let config_1 = TranslateConfig::builder(BuildInVoting::CombSum)
+            .top_candidate_limit(Some(1))
+            .build()
+            .unwrap();
+        let config_2 = TranslateConfig::builder(BuildInVoting::PCombSum)
+            .top_candidate_limit(Some(3))
+            .build()
+            .unwrap();
+        let model_b_1 = translate_topic_model_without_provider(&model_a, &dict, &config_1).unwrap();
+        let model_b_2 = translate_topic_model_without_provider(&model_a, &dict, &config_2).unwrap();
+
+        let ensemble = TopicModel::ensemble(&[&model_b_1, &model_b_2], &[1.0, 3.0]).unwrap();
+        assert_eq!(2, ensemble.k());
+        for word in model_b_1.vocabulary().iter().chain(model_b_2.vocabulary().iter()) {
+            assert!(ensemble.vocabulary().contains(word.as_str()), "{word} is missing");
+        }
+        for topic in ensemble.topics() {
+            assert!((topic.iter().sum::<f64>() - 1.0).abs() < 1E-10);
+        }
+
+        let only_second = TopicModel::ensemble(&[&model_b_1, &model_b_2], &[0.0, 1.0]).unwrap();
+        for (word_id, word) in model_b_2.vocabulary().iter().enumerate() {
+            let ensemble_id = only_second.vocabulary().get_id(word.as_str()).unwrap();
+            for (topic, ensemble_topic) in model_b_2.topics().iter().zip_eq(only_second.topics()) {
+                assert!(ensemble_topic[ensemble_id] <= topic[word_id] + 1E-10);
+            }
+        }
+
+        let single_topic = model_b_1.without_topics(&[1]);
+        assert_eq!(
+            EnsembleError::TopicCountMismatch { model: 1, expected: 2, actual: 1 },
+            TopicModel::ensemble(&[&model_b_1, &single_topic], &[1.0, 1.0]).unwrap_err()
+        );
+        assert_eq!(
+            EnsembleError::WeightCountMismatch { models: 2, weights: 1 },
+            TopicModel::ensemble(&[&model_b_1, &model_b_2], &[1.0]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn all_parallelism_modes_translate_identically(){
+        let (voc_a, _, dict) = create_test_data();
+
+        let model_a = create_test_model(voc_a);
+
+        let translations = ParallelismMode::VARIANTS.iter().map(|mode| {
+            let config = TranslateConfig::builder(BuildInVoting::PCombSum)
+                .keep_original_word(Never)
+                .top_candidate_limit(Some(3))
+                .parallelism(*mode)
+                .build()
+                .unwrap();
+            let model_b = translate_topic_model_without_provider(
+                &model_a,
+                &dict,
+                &config,
+            ).unwrap_or_else(|err| panic!("{mode} failed with {err}"));
+            (model_b.topics().clone(), model_b.vocabulary().iter().map(|value| value.to_string()).collect_vec())
+        }).collect_vec();
+
+        for (mode, translation) in ParallelismMode::VARIANTS.iter().zip_eq(translations.iter()) {
+            assert_eq!(&translations[0], translation, "{mode} differs from {}", ParallelismMode::VARIANTS[0]);
+        }
     }
 }
 
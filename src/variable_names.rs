@@ -153,11 +153,35 @@ declare_variable_names! {
     IMPORTANCE: "importance",
     doc = "The score of the word in the topic model."
     SCORE: "score",
+    doc = "The external weight of a voter, 1.0 if not set."
+    WEIGHT: "weight",
     doc = "The word id of a voter."
     VOTER_ID: "voter_id",
     doc = "The word id of a candidate."
     CANDIDATE_ID: "candidate_id",
     doc = "The topic id."
-    TOPIC_ID: "topic_id"
+    TOPIC_ID: "topic_id",
+    doc = "The cosine similarity between two topics."
+    TOPIC_SIMILARITY_SCORE: "topic_similarity",
+    doc = "The cosine similarity between the embedding of a voter and the centroid of the topic."
+    EMBEDDING_SIMILARITY_A: "embedding_similarity_a",
+    doc = "The cosine similarity between the embedding of a candidate and the centroid of the topic."
+    EMBEDDING_SIMILARITY_B: "embedding_similarity_b"
 }
 
+
+/// All variables set in the context of a voter.
+pub const VOTER_VARIABLE_NAMES: [&str; 7] = [
+    RECIPROCAL_RANK,
+    REAL_RECIPROCAL_RANK,
+    RANK,
+    IMPORTANCE,
+    SCORE,
+    WEIGHT,
+    VOTER_ID
+];
+
+/// The variables of a voter that are only set by a variable provider.
+pub const PROVIDED_VOTER_VARIABLE_NAMES: [&str; 1] = [
+    EMBEDDING_SIMILARITY_A
+];
@@ -12,13 +12,13 @@
 //See the License for the specific language governing permissions and
 //limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter, Write};
 use std::num::NonZeroUsize;
-use evalexpr::{ContextWithMutableVariables, IterateVariablesContext, Value};
-use crate::variable_names::{NUMBER_OF_VOTERS, RANK};
+use evalexpr::{ContextWithMutableVariables, HashMapContext, IterateVariablesContext, Value};
+use crate::variable_names::{IMPORTANCE, NUMBER_OF_VOTERS, RANK, RECIPROCAL_RANK, SCORE};
 pub use crate::voting::buildin::*;
-use crate::voting::display::{DisplayTree, IndentWriter};
+use crate::voting::display::{fmt_display_tree, DisplayTree, IndentWriter};
 pub use crate::voting::parser::voting_function::VotingFunction;
 pub use crate::voting::errors::VotingExpressionError;
 use crate::voting::traits::{RootVotingMethodMarker, VotingMethodMarker};
@@ -31,6 +31,7 @@ mod walk;
 pub mod display;
 pub mod errors;
 pub mod spy;
+pub mod audit;
 pub mod traits;
 pub mod py;
 
@@ -40,6 +41,11 @@ pub type VotingResult<T> = Result<T, VotingExpressionError>;
 /// A voting method context allows to create a variable map to something that can me handled by python.
 pub trait VotingMethodContext : ContextWithMutableVariables {
     fn variable_map(&self) -> HashMap<String, Value>;
+
+    /// Same as [VotingMethodContext::variable_map] but ordered by the variable names.
+    fn variable_map_ordered(&self) -> BTreeMap<String, Value> {
+        self.variable_map().into_iter().collect()
+    }
 }
 impl<T> VotingMethodContext for T where T: ContextWithMutableVariables + IterateVariablesContext {
     fn variable_map(&self) -> HashMap<String, Value> {
@@ -79,6 +85,12 @@ pub trait VotingMethod {
             B : VotingMethodContext {
         Ok((self.execute(global_context, voters)?, voters))
     }
+
+    /// The names of the variables this method reads from the voter contexts.
+    /// Used to validate the voter contexts before executing a voting.
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        vec![SCORE, RANK, IMPORTANCE, RECIPROCAL_RANK]
+    }
 }
 
 
@@ -125,6 +137,14 @@ impl<T> VotingMethod for VotingWithLimit<T> where T: VotingMethodMarker {
         global_context.set_value(NUMBER_OF_VOTERS.to_string(), (voters.len() as i64).into())?;
         self.expr.execute_with_voters(global_context, voters)
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        let mut fields = self.expr.required_voter_fields();
+        if !fields.contains(&RANK) {
+            fields.push(RANK);
+        }
+        fields
+    }
 }
 
 impl<T> DisplayTree for VotingWithLimit<T> where T: DisplayTree {
@@ -136,10 +156,292 @@ impl<T> DisplayTree for VotingWithLimit<T> where T: DisplayTree {
 
 impl<T> Display for VotingWithLimit<T> where T: DisplayTree {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut code_formatter = IndentWriter::new(f);
-        DisplayTree::fmt(self, &mut code_formatter)
+        fmt_display_tree(self, f)
     }
 }
 
+/// A voting with limits, the voters are selected by a weight instead of their rank.
+///
+/// The weight of a voter is the result of `weight_expr` executed with only this voter,
+/// e.g. `aggregate(let w = sumOf): score * importance`. The voters with the highest weights are used.
+/// The `weight_expr` works on a copy of the global context, the variables it sets are discarded.
+#[derive(Debug, Clone)]
+pub struct VotingWithLimitWeighted<T: ?Sized> {
+    /// The limit for the votes
+    limit: NonZeroUsize,
+    weight_expr: VotingFunction,
+    expr: T
+}
+
+impl<T> VotingWithLimitWeighted<T> {
+    /// Fails if `weight_expr` does not only work with the variables of the voters.
+    /// (See [VotingFunction::validate_voter_level])
+    pub fn new(limit: NonZeroUsize, weight_expr: VotingFunction, expr: T) -> VotingResult<Self> {
+        weight_expr.validate_voter_level()?;
+        Ok(
+            Self {
+                limit,
+                weight_expr,
+                expr
+            }
+        )
+    }
+
+    fn slice_voters<'a, A, B>(&self, global_context: &A, voters: &'a mut [B]) -> VotingResult<&'a mut [B]> where A: VotingMethodContext, B: VotingMethodContext {
+        let limit = self.limit.get();
+        if limit >= voters.len() {
+            return Ok(voters)
+        }
+        let mut scratch = HashMapContext::new();
+        for (key, value) in global_context.variable_map() {
+            scratch.set_value(key, value)?;
+        }
+        let weights = voters
+            .iter_mut()
+            .map(|voter| self.weight_expr.execute_to_f64(&mut scratch.clone(), std::slice::from_mut(voter)))
+            .collect::<VotingResult<Vec<_>>>()?;
+        let mut order = (0..voters.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+        order.truncate(limit);
+        move_positions_to_front(voters, &order);
+        Ok(&mut voters[..limit])
+    }
+}
 
+impl<T> RootVotingMethodMarker for VotingWithLimitWeighted<T> where T: VotingMethodMarker {}
+impl<T> VotingMethodMarker for VotingWithLimitWeighted<T> where T: VotingMethodMarker {}
+impl<T> VotingMethod for VotingWithLimitWeighted<T> where T: VotingMethodMarker {
+    fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+        let voters = self.slice_voters(global_context, voters)?;
+        global_context.set_value(NUMBER_OF_VOTERS.to_string(), (voters.len() as i64).into())?;
+        self.expr.execute(global_context, voters)
+    }
 
+    fn execute_with_voters<'a, A, B>(&self, global_context: &mut A, voters: &'a mut [B]) -> VotingResult<(Value, &'a [B])> where A: VotingMethodContext, B: VotingMethodContext {
+        let voters = self.slice_voters(global_context, voters)?;
+        global_context.set_value(NUMBER_OF_VOTERS.to_string(), (voters.len() as i64).into())?;
+        self.expr.execute_with_voters(global_context, voters)
+    }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        let mut fields = self.expr.required_voter_fields();
+        for field in self.weight_expr.required_variables() {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        fields
+    }
+}
+
+impl<T> DisplayTree for VotingWithLimitWeighted<T> where T: DisplayTree {
+    fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result {
+        DisplayTree::fmt(&self.expr, f)?;
+        write!(f, "({} by ", self.limit.get())?;
+        DisplayTree::fmt(&self.weight_expr, f)?;
+        write!(f, ")")
+    }
+}
+
+impl<T> Display for VotingWithLimitWeighted<T> where T: DisplayTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_display_tree(self, f)
+    }
+}
+
+/// Moves the voters at the positions in `order` to the front, keeping the order of `order`.
+/// The order of the remaining voters is unspecified.
+pub(crate) fn move_positions_to_front<B>(voters: &mut [B], order: &[usize]) {
+    let mut at_position = (0..voters.len()).collect::<Vec<_>>();
+    let mut position_of = (0..voters.len()).collect::<Vec<_>>();
+    for (target, original) in order.iter().copied().enumerate() {
+        let current = position_of[original];
+        let displaced = at_position[target];
+        voters.swap(target, current);
+        at_position.swap(target, current);
+        position_of[original] = target;
+        position_of[displaced] = current;
+    }
+}
+
+/// Executes the `primary` voting and falls back to the `fallback` voting if the evaluation of an
+/// expression of the primary one fails, all other errors are returned.
+/// The primary voting may already have modified the contexts when the fallback is executed.
+#[derive(Debug, Clone)]
+pub struct FallbackVoting<P, F> {
+    primary: P,
+    fallback: F
+}
+
+impl<P, F> FallbackVoting<P, F> {
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+impl<P, F> RootVotingMethodMarker for FallbackVoting<P, F> where P: VotingMethodMarker, F: VotingMethodMarker {}
+impl<P, F> VotingMethodMarker for FallbackVoting<P, F> where P: VotingMethodMarker, F: VotingMethodMarker {}
+impl<P, F> VotingMethod for FallbackVoting<P, F> where P: VotingMethodMarker, F: VotingMethodMarker {
+    fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+        match self.primary.execute(global_context, voters) {
+            Err(VotingExpressionError::Eval(err)) => {
+                log::warn!("The primary voting failed with \"{err}\", using the fallback.");
+                self.fallback.execute(global_context, voters)
+            }
+            other => other
+        }
+    }
+
+    fn execute_with_voters<'a, A, B>(&self, global_context: &mut A, voters: &'a mut [B]) -> VotingResult<(Value, &'a [B])> where A: VotingMethodContext, B: VotingMethodContext {
+        // The used voters are always a prefix of the voters.
+        let primary = self.primary.execute_with_voters(global_context, &mut *voters).map(|(value, used)| (value, used.len()));
+        match primary {
+            Ok((value, used)) => Ok((value, &voters[..used])),
+            Err(VotingExpressionError::Eval(err)) => {
+                log::warn!("The primary voting failed with \"{err}\", using the fallback.");
+                self.fallback.execute_with_voters(global_context, voters)
+            }
+            Err(err) => Err(err)
+        }
+    }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        let mut fields = self.primary.required_voter_fields();
+        for field in self.fallback.required_voter_fields() {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        fields
+    }
+}
+
+impl<P, F> DisplayTree for FallbackVoting<P, F> where P: DisplayTree, F: DisplayTree {
+    fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result {
+        DisplayTree::fmt(&self.primary, f)?;
+        write!(f, " else ")?;
+        DisplayTree::fmt(&self.fallback, f)
+    }
+}
+
+impl<P, F> Display for FallbackVoting<P, F> where P: DisplayTree, F: DisplayTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_display_tree(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use evalexpr::{context_map, Context, HashMapContext, Value};
+    use crate::variable_names::{EPSILON, IMPORTANCE, NUMBER_OF_VOTERS, RANK, RECIPROCAL_RANK, SCORE, SCORE_CANDIDATE, VOTER_ID};
+    use std::num::NonZeroUsize;
+    use crate::voting::{BuildInVoting, FallbackVoting, VotingExpressionError, VotingFunction, VotingMethod, VotingMethodContext, VotingResult, VotingWithLimitWeighted};
+    use crate::voting::traits::VotingMethodMarker;
+    use crate::voting::parser::logic::global_voting_function;
+
+    #[test]
+    fn variable_map_ordered_is_sorted(){
+        let context = context_map! {
+            SCORE => 0.5,
+            VOTER_ID => 3,
+            RANK => 1,
+            RECIPROCAL_RANK => 1.0,
+            IMPORTANCE => 1
+        }.unwrap();
+        let keys = context.variable_map_ordered().into_keys().collect::<Vec<_>>();
+        let mut expected = vec![SCORE, VOTER_ID, RANK, RECIPROCAL_RANK, IMPORTANCE];
+        expected.sort();
+        assert_eq!(expected, keys);
+        assert_eq!(keys, context.variable_map_ordered().into_keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn fallback_is_used_if_the_primary_voting_fails(){
+        let voting = FallbackVoting::new(BuildInVoting::CombSum, BuildInVoting::OriginalScore);
+        assert_eq!(vec![SCORE], voting.required_voter_fields());
+        assert_eq!("CombSum else OriginalScore", voting.to_string());
+
+        let mut global = context_map! {
+            SCORE_CANDIDATE => 0.25
+        }.unwrap();
+
+        // CombSum fails because the voters have no score.
+        let mut voters = [context_map! { RANK => 1 }.unwrap()];
+        assert!(BuildInVoting::CombSum.execute(&mut global, &mut voters).is_err());
+        assert_eq!(0.25, voting.execute_to_f64(&mut global, &mut voters).unwrap());
+
+        let mut voters: [HashMapContext; 2] = [context_map! { SCORE => 0.5 }.unwrap(), context_map! { SCORE => 0.25 }.unwrap()];
+        let (result, used) = voting.execute_with_voters(&mut global, &mut voters).unwrap();
+        assert_eq!(0.75, result.as_number().unwrap());
+        assert_eq!(2, used.len());
+
+        // Only the errors of an evaluation trigger the fallback.
+        let voting = FallbackVoting::new(AlwaysNoValue, BuildInVoting::OriginalScore);
+        assert!(matches!(voting.execute(&mut global, &mut voters), Err(VotingExpressionError::NoValue)));
+        assert!(matches!(voting.execute_with_voters(&mut global, &mut voters), Err(VotingExpressionError::NoValue)));
+    }
+
+    /// Always fails without evaluating anything.
+    #[derive(Debug)]
+    struct AlwaysNoValue;
+
+    impl VotingMethod for AlwaysNoValue {
+        fn execute<A, B>(&self, _: &mut A, _: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+            Err(VotingExpressionError::NoValue)
+        }
+    }
+
+    impl VotingMethodMarker for AlwaysNoValue {}
+
+    fn parse_function(value: &str) -> VotingFunction {
+        global_voting_function::<nom::error::Error<_>>(value.into()).unwrap().1
+    }
+
+    #[test]
+    fn weighted_limit_selects_voters_by_weight(){
+        let voting = VotingWithLimitWeighted::new(
+            NonZeroUsize::new(1).unwrap(),
+            parse_function("aggregate(let w = sumOf): importance"),
+            BuildInVoting::CombSum
+        ).unwrap();
+        assert!(voting.to_string().starts_with("CombSum(1 by "));
+        assert!(voting.required_voter_fields().contains(&IMPORTANCE));
+
+        let mut global = context_map! {
+            SCORE_CANDIDATE => 1.0
+        }.unwrap();
+        // The best ranked voter has the lowest importance.
+        let mut voters = [
+            context_map! { VOTER_ID => 0, RANK => 1, SCORE => 0.5, IMPORTANCE => 1 }.unwrap(),
+            context_map! { VOTER_ID => 1, RANK => 2, SCORE => 0.25, IMPORTANCE => 3 }.unwrap(),
+            context_map! { VOTER_ID => 2, RANK => 3, SCORE => 0.125, IMPORTANCE => 2 }.unwrap(),
+        ];
+        let (result, used) = voting.execute_with_voters(&mut global, &mut voters).unwrap();
+        assert_eq!(0.25, result.as_number().unwrap());
+        assert_eq!(1, used.len());
+        assert_eq!(1, used[0].get_value(VOTER_ID).unwrap().as_int().unwrap());
+        assert_eq!(1, global.get_value(NUMBER_OF_VOTERS).unwrap().as_int().unwrap());
+        // The variables of the weight expression do not leak into the global context.
+        assert!(global.get_value("w").is_none());
+    }
+
+    #[test]
+    fn weighted_limit_rejects_global_variables(){
+        for weight in [
+            format!("aggregate(let w = sumOf): {SCORE} * {EPSILON}"),
+            format!("global: {SCORE_CANDIDATE}"),
+        ] {
+            match VotingWithLimitWeighted::new(NonZeroUsize::new(1).unwrap(), parse_function(&weight), BuildInVoting::CombSum) {
+                Err(VotingExpressionError::NotVoterLevel(_)) => {}
+                other => panic!("Expected an error for {weight} but got {other:?}")
+            }
+        }
+        assert!(
+            VotingWithLimitWeighted::new(
+                NonZeroUsize::new(1).unwrap(),
+                parse_function(&format!("aggregate(let w = sumOf): {{\n let x = {SCORE} * 2\n x\n}}")),
+                BuildInVoting::CombSum
+            ).is_ok()
+        );
+    }
+}
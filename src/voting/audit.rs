@@ -0,0 +1,112 @@
+//Copyright 2024 Felix Engl
+//
+//Licensed under the Apache License, Version 2.0 (the "License");
+//you may not use this file except in compliance with the License.
+//You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//Unless required by applicable law or agreed to in writing, software
+//distributed under the License is distributed on an "AS IS" BASIS,
+//WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//See the License for the specific language governing permissions and
+//limitations under the License.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use evalexpr::{Context, ContextWithMutableVariables, EvalexprResult, Value};
+use crate::voting::VotingMethodContext;
+
+/// Wraps a context and records the names of all variables read from it.
+/// Works with everything that dereferences to a [VotingMethodContext], e.g. `&mut HashMapContext`.
+#[derive(Debug)]
+pub struct AuditingContext<C> {
+    inner: C,
+    accesses: RefCell<Vec<String>>
+}
+
+impl<C> AuditingContext<C> {
+    pub fn new(inner: C) -> Self {
+        Self { inner, accesses: Default::default() }
+    }
+
+    /// The names of the variables read so far, in the order of access.
+    pub fn accesses(&self) -> Vec<String> {
+        self.accesses.borrow().clone()
+    }
+
+    /// Returns the wrapped context and the names of all variables read.
+    pub fn into_audit_log(self) -> (C, Vec<String>) {
+        (self.inner, self.accesses.into_inner())
+    }
+}
+
+impl<C> AuditingContext<C> where C: Deref {
+    /// The wrapped context, reading from it is not recorded.
+    pub fn get_ref(&self) -> &C::Target {
+        self.inner.deref()
+    }
+}
+
+impl<C> Context for AuditingContext<C> where C: DerefMut, C::Target: Context {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.accesses.borrow_mut().push(identifier.to_string());
+        self.inner.get_value(identifier)
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.inner.call_function(identifier, argument)
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.inner.are_builtin_functions_disabled()
+    }
+
+    fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+        self.inner.set_builtin_functions_disabled(disabled)
+    }
+}
+
+impl<C> ContextWithMutableVariables for AuditingContext<C> where C: DerefMut, C::Target: ContextWithMutableVariables {
+    fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+        self.inner.set_value(identifier, value)
+    }
+}
+
+impl<C> VotingMethodContext for AuditingContext<C> where C: DerefMut, C::Target: VotingMethodContext {
+    fn variable_map(&self) -> HashMap<String, Value> {
+        self.inner.variable_map()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use evalexpr::{ContextWithMutableVariables, HashMapContext};
+    use itertools::Itertools;
+    use crate::variable_names::{NUMBER_OF_VOTERS, RECIPROCAL_RANK, SCORE};
+    use crate::voting::audit::AuditingContext;
+    use crate::voting::{BuildInVoting, VotingMethod};
+
+    #[test]
+    fn pcombsum_reads_the_expected_variables(){
+        let mut global = HashMapContext::new();
+        global.set_value(NUMBER_OF_VOTERS.to_string(), 2.into()).unwrap();
+        let mut voters = (1..=2).map(|rank| {
+            let mut voter = HashMapContext::new();
+            voter.set_value(SCORE.to_string(), 0.5.into()).unwrap();
+            voter.set_value(RECIPROCAL_RANK.to_string(), (1.0 / rank as f64).into()).unwrap();
+            voter
+        }).collect_vec();
+
+        let mut audited_global = AuditingContext::new(&mut global);
+        let mut audited_voters = voters.iter_mut().map(AuditingContext::new).collect_vec();
+        let result = BuildInVoting::PCombSum.execute_to_f64(&mut audited_global, &mut audited_voters).unwrap();
+        assert_eq!(1.5, result);
+
+        assert_eq!(vec![NUMBER_OF_VOTERS.to_string()], audited_global.into_audit_log().1);
+        for voter in audited_voters {
+            assert_eq!(vec![SCORE.to_string(), RECIPROCAL_RANK.to_string()], voter.into_audit_log().1);
+        }
+    }
+}
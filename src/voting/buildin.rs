@@ -43,6 +43,10 @@ impl VotingMethod for EmptyVotingMethod {
     fn execute<A, B>(&self, _: &mut A, _: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
         return Err(NoValue)
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
 }
 
 /// All possible buildin votings
@@ -310,6 +314,37 @@ impl VotingMethod for BuildInVoting {
             }
         }
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        match self {
+            BuildInVoting::OriginalScore
+            | BuildInVoting::Voters => {
+                Vec::new()
+            }
+            BuildInVoting::CombSum
+            | BuildInVoting::GCombSum
+            | BuildInVoting::CombSumTop
+            | BuildInVoting::CombSumPow2
+            | BuildInVoting::CombMax
+            | BuildInVoting::ExpCombMnz
+            | BuildInVoting::WCombSum
+            | BuildInVoting::WCombSumG
+            | BuildInVoting::WGCombSum => {
+                vec![SCORE]
+            }
+            BuildInVoting::RR
+            | BuildInVoting::RRPow2 => {
+                vec![RECIPROCAL_RANK]
+            }
+            BuildInVoting::CombSumRR
+            | BuildInVoting::CombSumRRPow2
+            | BuildInVoting::CombSumPow2RR
+            | BuildInVoting::CombSumPow2RRPow2
+            | BuildInVoting::PCombSum => {
+                vec![SCORE, RECIPROCAL_RANK]
+            }
+        }
+    }
 }
 impl DisplayTree for BuildInVoting {
     fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result {
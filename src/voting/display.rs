@@ -12,15 +12,14 @@
 //See the License for the specific language governing permissions and
 //limitations under the License.
 
-use std::fmt::{Display, Write};
+use std::fmt::{Display, Formatter, Write};
 
 macro_rules! impl_display_for_displaytree {
     ($($target: ident),+) => {
         $(
             impl Display for $target {
                 fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-                    let mut code_formatter = $crate::voting::display::IndentWriter::new(f);
-                    $crate::voting::display::DisplayTree::fmt(self, &mut code_formatter)
+                    $crate::voting::display::fmt_display_tree(self, f)
                 }
             }
         )+
@@ -33,10 +32,29 @@ pub trait DisplayTree: Display {
     fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result;
 }
 
+/// Formats a [DisplayTree] with the options of the formatter.
+///
+/// The alternate flag (`{:#}`) selects the compact mode and the width (e.g. `{:4}`)
+/// sets the indent width, see [IndentWriter::compact] and [IndentWriter::with_indent].
+pub fn fmt_display_tree<D: DisplayTree + ?Sized>(value: &D, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let compact = f.alternate();
+    let width = f.width();
+    let mut code_formatter = IndentWriter::new(f).compact(compact);
+    if let Some(width) = width {
+        code_formatter = code_formatter.with_indent(width);
+    }
+    DisplayTree::fmt(value, &mut code_formatter)
+}
+
+/// The default number of spaces per indent level.
+pub const DEFAULT_INDENT_WIDTH: usize = 2;
+
 /// Writes something with leveled indent
 pub struct IndentWriter<'a, T: Write> {
     f: &'a mut T,
     level: usize,
+    indent_width: usize,
+    compact: bool,
     indent: String
 }
 
@@ -45,28 +63,97 @@ impl<'a, T> IndentWriter<'a, T> where T: Write {
         Self {
             f,
             level: 0,
+            indent_width: DEFAULT_INDENT_WIDTH,
+            compact: false,
             indent: String::new()
         }
     }
 
+    /// Sets the number of spaces per indent level.
+    pub fn with_indent(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self.update_indent();
+        self
+    }
+
+    /// If set, newlines are replaced by single spaces and no indent is written.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    pub fn indent_width(&self) -> usize {
+        self.indent_width
+    }
+
+    /// Increases the indent by `value` levels.
     pub fn indent(&mut self, value: usize) {
         self.level = self.level.saturating_add(value);
-        self.indent = " ".repeat(self.level);
+        self.update_indent();
     }
 
+    /// Decreases the indent by `value` levels.
     pub fn dedent(&mut self, value: usize) {
         self.level = self.level.saturating_sub(value);
-        self.indent = " ".repeat(self.level);
+        self.update_indent();
+    }
+
+    fn update_indent(&mut self) {
+        self.indent = " ".repeat(self.level.saturating_mul(self.indent_width));
     }
 }
 
 impl<T> Write for IndentWriter<'_, T> where T: Write {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
-        if s.ends_with("\n") {
+        if self.compact {
+            write!(self.f, "{}", s.replace('\n', " "))
+        } else if s.ends_with("\n") {
             write!(self.f, "{}{}", s, self.indent)
         } else {
             write!(self.f, "{}", s)
         }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroUsize;
+    use crate::voting::parser::logic::voting;
+    use crate::voting::display::{DisplayTree, IndentWriter};
+    use crate::voting::VotingWithLimit;
+
+    const EXPR: &str = "declare my_voting {
+        aggregate(let sss = sumOf): {score}
+        global: sss
+    }";
+
+    #[test]
+    fn can_render_with_indent_and_compact(){
+        let expr = voting::<nom::error::Error<_>>(EXPR.into()).unwrap().1;
+
+        let default = expr.to_string();
+        assert!(default.contains("\n  "));
+
+        let mut wide = String::new();
+        DisplayTree::fmt(&expr, &mut IndentWriter::new(&mut wide).with_indent(4)).unwrap();
+        let expected = default.lines().map(|line| {
+            let content = line.trim_start_matches(' ');
+            format!("{}{}", " ".repeat(2 * (line.len() - content.len())), content)
+        }).collect::<Vec<_>>().join("\n");
+        assert_eq!(expected, wide);
+        assert_eq!(wide, format!("{expr:4}"));
+
+        let compact = format!("{expr:#}");
+        assert!(!compact.contains('\n'));
+        assert_eq!(default.split_whitespace().collect::<Vec<_>>(), compact.split_whitespace().collect::<Vec<_>>());
 
+        let limited = VotingWithLimit::new(NonZeroUsize::new(3).unwrap(), expr);
+        let limited_compact = format!("{limited:#}");
+        assert!(!limited_compact.contains('\n'));
+        assert_eq!(format!("{compact}(3)"), limited_compact);
     }
 }
@@ -29,6 +29,8 @@ pub enum VotingExpressionError {
     TupleGet(String, IndexOrRange, usize),
     #[error("No value for working with was found!")]
     NoValue,
+    #[error("{0} is not available on the voter level!")]
+    NotVoterLevel(String),
     #[error(transparent)]
     PythonError(PyErr)
 }
@@ -12,17 +12,21 @@
 //See the License for the specific language governing permissions and
 //limitations under the License.
 
+use std::fmt::{Display, Formatter, Write};
 use std::sync::Arc;
 use evalexpr::{Value};
 use nom::branch::alt;
 use nom::combinator::{map, map_res};
 use nom::IResult;
 use strum::EnumIs;
-use crate::voting::{BuildInVoting, VotingFunction, VotingMethod, VotingMethodContext, VotingResult, VotingWithLimit};
+use crate::voting::{BuildInVoting, FallbackVoting, VotingFunction, VotingMethod, VotingMethodContext, VotingResult, VotingWithLimit};
 use crate::voting::parser::input::ParserInput;
 use crate::voting::parser::logic::{build_in_voting, ErrorType, global_voting_function, parse_limited, variable_name, voting};
 use crate::voting::parser::logic::VotingParseError::{NoRegistryProvided, NoVotingInRegistryFound};
+use crate::voting::display::{DisplayTree, IndentWriter};
+use crate::voting::display::impl_display_for_displaytree;
 use crate::voting::parser::voting_function::VotingAndName;
+use crate::voting::registry::DynVotingMethod;
 use crate::voting::traits::VotingMethodMarker;
 
 pub(crate) mod voting_function;
@@ -41,8 +45,13 @@ pub fn parse<'a, 'b, E: ErrorType<ParserInput<'a,'b>>>(input: ParserInput<'a,'b>
                 Some(registry) => {
                     registry
                         .get(value.as_ref())
-                        .ok_or_else(|| NoVotingInRegistryFound(value.to_string()))
                         .map(InterpretedVoting::FromRegistry)
+                        .or_else(|| {
+                            registry
+                                .get_method(value.as_ref())
+                                .map(|method| InterpretedVoting::Registered(value.to_string(), method))
+                        })
+                        .ok_or_else(|| NoVotingInRegistryFound(value.to_string()))
                 }
             }),
             map(voting, InterpretedVoting::ForRegistry),
@@ -61,9 +70,11 @@ pub fn parse<'a, 'b, E: ErrorType<ParserInput<'a,'b>>>(input: ParserInput<'a,'b>
 pub enum InterpretedVoting {
     BuildIn(BuildInVoting),
     FromRegistry(Arc<VotingFunction>),
+    Registered(String, Arc<dyn DynVotingMethod>),
     Parsed(VotingFunction),
     ForRegistry(VotingAndName),
     Limited(VotingWithLimit<Box<InterpretedVoting>>),
+    Fallback(FallbackVoting<Box<InterpretedVoting>, Box<InterpretedVoting>>),
 }
 
 impl VotingMethodMarker for InterpretedVoting {}
@@ -77,6 +88,10 @@ impl VotingMethod for InterpretedVoting {
             InterpretedVoting::FromRegistry(value) => {
                 value.execute(global_context, voters)
             }
+            InterpretedVoting::Registered(_, value) => {
+                let mut voters = voters.iter_mut().map(|voter| voter as &mut dyn VotingMethodContext).collect::<Vec<_>>();
+                value.execute_dyn(global_context, voters.as_mut_slice())
+            }
             InterpretedVoting::Parsed(value) => {
                 value.execute(global_context, voters)
             }
@@ -86,10 +101,69 @@ impl VotingMethod for InterpretedVoting {
             InterpretedVoting::Limited(value) => {
                 value.execute(global_context, voters)
             }
+            InterpretedVoting::Fallback(value) => {
+                value.execute(global_context, voters)
+            }
+        }
+    }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        match self {
+            InterpretedVoting::BuildIn(value) => {
+                value.required_voter_fields()
+            }
+            InterpretedVoting::FromRegistry(value) => {
+                value.required_voter_fields()
+            }
+            InterpretedVoting::Registered(_, value) => {
+                value.required_voter_fields_dyn()
+            }
+            InterpretedVoting::Parsed(value) => {
+                value.required_voter_fields()
+            }
+            InterpretedVoting::ForRegistry(value) => {
+                value.1.required_voter_fields()
+            }
+            InterpretedVoting::Limited(value) => {
+                value.required_voter_fields()
+            }
+            InterpretedVoting::Fallback(value) => {
+                value.required_voter_fields()
+            }
+        }
+    }
+}
+
+impl DisplayTree for InterpretedVoting {
+    fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result {
+        match self {
+            InterpretedVoting::BuildIn(value) => {
+                DisplayTree::fmt(value, f)
+            }
+            InterpretedVoting::FromRegistry(value) => {
+                DisplayTree::fmt(value.as_ref(), f)
+            }
+            InterpretedVoting::Registered(name, _) => {
+                write!(f, "{name}")
+            }
+            InterpretedVoting::Parsed(value) => {
+                DisplayTree::fmt(value, f)
+            }
+            InterpretedVoting::ForRegistry(value) => {
+                DisplayTree::fmt(value, f)
+            }
+            InterpretedVoting::Limited(value) => {
+                DisplayTree::fmt(value, f)
+            }
+            InterpretedVoting::Fallback(value) => {
+                DisplayTree::fmt(value, f)
+            }
         }
     }
 }
 
+impl_display_for_displaytree!(InterpretedVoting);
+
 impl From<Arc<VotingFunction>> for InterpretedVoting {
     fn from(value: Arc<VotingFunction>) -> Self {
         Self::FromRegistry(value)
@@ -124,6 +198,68 @@ mod test {
     use crate::voting::parser::logic::global_voting_function;
     use crate::voting::registry::VotingRegistry;
 
+    #[test]
+    fn can_recognize_registered_method(){
+        use crate::voting::VotingMethod;
+
+        #[derive(Debug)]
+        struct AlwaysFortyTwo;
+
+        impl VotingMethod for AlwaysFortyTwo {
+            fn execute<A, B>(&self, _: &mut A, voters: &mut [B]) -> crate::voting::VotingResult<evalexpr::Value> where A: crate::voting::VotingMethodContext, B: crate::voting::VotingMethodContext {
+                Ok(evalexpr::Value::Float(42.0 + voters.len() as f64))
+            }
+
+            fn required_voter_fields(&self) -> Vec<&'static str> {
+                Vec::new()
+            }
+        }
+
+        impl crate::voting::traits::VotingMethodMarker for AlwaysFortyTwo {}
+
+        let registry = VotingRegistry::new();
+        assert!(registry.register_method("forty_two".to_string(), AlwaysFortyTwo).is_none());
+
+        let result: IResult<_, _> = parse(ParserInput::new("forty_two", &registry));
+        let (_, result) = result.unwrap();
+        assert!(result.is_registered());
+        assert!(result.required_voter_fields().is_empty());
+
+        let mut global = evalexpr::HashMapContext::new();
+        let mut voters = vec![evalexpr::HashMapContext::new(), evalexpr::HashMapContext::new()];
+        assert_eq!(44.0, result.execute_to_f64(&mut global, voters.as_mut_slice()).unwrap());
+
+        // Build in votings can not be overridden.
+        let build_in = BuildInVoting::CombSum.to_string();
+        registry.register_method(build_in.clone(), AlwaysFortyTwo);
+        let result: IResult<_, _> = parse(ParserInput::new(build_in.as_str(), &registry));
+        assert!(result.unwrap().1.is_build_in());
+    }
+
+    #[test]
+    fn can_display_and_register_a_fallback(){
+        use crate::voting::{FallbackVoting, VotingMethod};
+
+        let fallback = InterpretedVoting::Fallback(FallbackVoting::new(
+            Box::new(InterpretedVoting::BuildIn(BuildInVoting::CombSum)),
+            Box::new(InterpretedVoting::BuildIn(BuildInVoting::OriginalScore))
+        ));
+        assert_eq!("CombSum else OriginalScore", fallback.to_string());
+
+        let registry = VotingRegistry::new();
+        registry.register_method("safe_comb_sum".to_string(), fallback);
+        let parsed = registry.parse_once("safe_comb_sum").unwrap();
+        assert!(parsed.is_registered());
+        assert_eq!("safe_comb_sum", parsed.to_string());
+
+        // The voters have no score, the fallback returns the score of the candidate.
+        let mut global = evalexpr::context_map! {
+            crate::variable_names::SCORE_CANDIDATE => 0.25
+        }.unwrap();
+        let mut voters = vec![evalexpr::HashMapContext::new()];
+        assert_eq!(0.25, parsed.execute_to_f64(&mut global, voters.as_mut_slice()).unwrap());
+    }
+
     #[test]
     fn can_recognize_buildin(){
         let build_ind = BuildInVoting::CombSumPow2RRPow2.to_string();
@@ -160,6 +296,24 @@ mod test {
         assert!(result.is_from_registry())
     }
 
+    #[test]
+    fn can_collect_required_voter_fields(){
+        use crate::variable_names::{RANK, RECIPROCAL_RANK, SCORE};
+        use crate::voting::VotingMethod;
+
+        let result = global_voting_function::<nom::error::Error<_>>("
+            aggregate(let sss = sumOf): { score * rr }
+            global: sss + rank
+        ".into()).unwrap().1;
+
+        let fields = result.required_variables();
+        assert!(fields.contains(&SCORE));
+        assert!(fields.contains(&RECIPROCAL_RANK));
+        assert!(!fields.contains(&RANK));
+        assert_eq!(vec![SCORE, RECIPROCAL_RANK], BuildInVoting::PCombSum.required_voter_fields());
+        assert!(BuildInVoting::OriginalScore.required_voter_fields().is_empty());
+    }
+
     #[test]
     fn can_recognize_parsed_multiline(){
         let result: Result<_, _> = parse::<nom::error::VerboseError<_>>("{
@@ -722,6 +722,45 @@ mod test {
         println!("{:?}", x);
     }
 
+    #[test]
+    fn simplify_preserves_execute(){
+        use rand::thread_rng;
+        use rand_distr::Distribution;
+
+        let original = voting::<VerboseError<_>>("declare foldable {
+            aggregate(let sss = sumOf): { score * (2 * 0.5) + (3 - 3) - (1.5 - 4) }
+            global: sss * (1 + 1) / (2 ^ 2)
+        }".into()).finish().unwrap().1.1;
+        let simplified = original.clone().simplify();
+
+        let simplified_text = simplified.to_string();
+        assert!(!simplified_text.contains("0.5"));
+        assert!(simplified_text.contains("score * 1.0"));
+        assert_ne!(original.to_string(), simplified_text);
+
+        let scores = rand_distr::Normal::new(0.0, 5.0).unwrap();
+        for _ in 0..100 {
+            let mut voters = scores
+                .sample_iter(&mut thread_rng())
+                .take(5)
+                .map(|score| {
+                    let mut voter = HashMapContext::new();
+                    voter.set_value(SCORE.to_string(), score.into()).unwrap();
+                    voter
+                })
+                .collect::<Vec<_>>();
+            let mut voters_simplified = voters.clone();
+            let mut global = HashMapContext::new();
+            global.set_value(NUMBER_OF_VOTERS.to_string(), 5.into()).unwrap();
+            let mut global_simplified = global.clone();
+
+            assert_eq!(
+                original.execute(&mut global, &mut voters).unwrap(),
+                simplified.execute(&mut global_simplified, &mut voters_simplified).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn versuch2(){
         let mut conbtext = HashMapContext::new();
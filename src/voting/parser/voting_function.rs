@@ -18,12 +18,13 @@ use std::sync::Arc;
 use evalexpr::{ContextWithMutableVariables, EvalexprError, EvalexprResult, Node, TupleType, Value};
 use itertools::{FoldWhile, Itertools, Position};
 use crate::toolkit::evalexpr::CombineableContext;
+use crate::variable_names::{PROVIDED_VOTER_VARIABLE_NAMES, VOTER_VARIABLE_NAMES};
 use crate::voting::{BuildInVoting, VotingExpressionError, VotingMethod, VotingMethodContext, VotingMethodMarker, VotingResult, VotingWithLimit};
 use crate::voting::aggregations::Aggregation;
 use crate::voting::display::{DisplayTree, IndentWriter};
 use crate::voting::parser::traits::VotingExecutable;
 use crate::voting::traits::LimitableVotingMethodMarker;
-use crate::voting::walk::walk_left_to_right;
+use crate::voting::walk::{fold_constants, walk_left_to_right};
 use crate::voting::display::impl_display_for_displaytree;
 use crate::voting::parser::input::ParserInput;
 
@@ -54,10 +55,10 @@ impl DisplayTree for VotingAndName {
     fn fmt(&self, f: &mut IndentWriter<'_, impl Write>) -> std::fmt::Result {
         write!(f, "declare {} ", self.0)?;
         write!(f, "{{")?;
-        f.indent(2);
+        f.indent(1);
         write!(f, "\n")?;
         DisplayTree::fmt(&self.1, f)?;
-        f.dedent(2);
+        f.dedent(1);
         write!(f, "\n")?;
         write!(f, "}}")
     }
@@ -74,6 +75,102 @@ pub enum VotingFunction {
 }
 
 
+impl VotingFunction {
+    /// Simplifies the function by folding constant subexpressions to literals.
+    ///
+    /// Only subexpressions without variables and function calls are folded, and only if the
+    /// literal evaluates to exactly the same value. Non-finite floats and failing subexpressions
+    /// are never folded. Identity operations like `x * 1` are kept, because their result depends
+    /// on the type of `x` at runtime.
+    pub fn simplify(self) -> Self {
+        match self {
+            VotingFunction::Single(value, was_root) => {
+                VotingFunction::Single(value.simplify(), was_root)
+            }
+            VotingFunction::Multi(values) => {
+                VotingFunction::Multi(values.into_iter().map(VotingOperation::simplify).collect())
+            }
+        }
+    }
+
+    /// Returns the names of the voter variables read by this function.
+    pub fn required_variables(&self) -> Vec<&'static str> {
+        let mut target = Vec::new();
+        match self {
+            VotingFunction::Single(value, _) => {
+                value.collect_voter_variables(&mut target)
+            }
+            VotingFunction::Multi(values) => {
+                for value in values {
+                    value.collect_voter_variables(&mut target)
+                }
+            }
+        }
+        target
+    }
+
+    /// Checks that this function only works with the variables of a single voter.
+    /// Only `foreach` and `aggregate` operations are allowed and they can only read voter variables
+    /// or variables written by this function.
+    pub fn validate_voter_level(&self) -> VotingResult<()> {
+        let operations = match self {
+            VotingFunction::Single(value, _) => std::slice::from_ref(value),
+            VotingFunction::Multi(values) => values.as_slice()
+        };
+        let mut written = Vec::new();
+        for operation in operations {
+            match operation {
+                VotingOperation::IterScope { expr } => {
+                    expr.visit_variables(&mut |access| if let VariableAccess::Write(name) = access {
+                        written.push(name.to_string())
+                    });
+                }
+                VotingOperation::AggregationScope { variable_name, expr, .. } => {
+                    expr.visit_variables(&mut |access| if let VariableAccess::Write(name) = access {
+                        written.push(name.to_string())
+                    });
+                    written.push(variable_name.clone());
+                }
+                VotingOperation::GlobalScope { .. } => {
+                    return Err(VotingExpressionError::NotVoterLevel("global".to_string()))
+                }
+                VotingOperation::Execute { .. } => {
+                    return Err(VotingExpressionError::NotVoterLevel("execute".to_string()))
+                }
+            }
+        }
+        let mut result = Ok(());
+        for operation in operations {
+            if let VotingOperation::IterScope { expr } | VotingOperation::AggregationScope { expr, .. } = operation {
+                expr.visit_variables(&mut |access| {
+                    if let VariableAccess::Read(name) = access {
+                        if result.is_ok() && !VOTER_VARIABLE_NAMES.contains(&name) && !PROVIDED_VOTER_VARIABLE_NAMES.contains(&name) && !written.iter().any(|value| value == name) {
+                            result = Err(VotingExpressionError::NotVoterLevel(name.to_string()))
+                        }
+                    }
+                });
+            }
+        }
+        result
+    }
+}
+
+/// A variable read or written by an expression.
+#[derive(Debug, Copy, Clone)]
+enum VariableAccess<'a> {
+    Read(&'a str),
+    Write(&'a str)
+}
+
+/// Pushes `name` to `target` if it is the name of a voter variable.
+fn push_voter_variable(name: &str, target: &mut Vec<&'static str>) {
+    if let Some(found) = VOTER_VARIABLE_NAMES.iter().chain(PROVIDED_VOTER_VARIABLE_NAMES.iter()).find(|value| **value == name) {
+        if !target.contains(found) {
+            target.push(found)
+        }
+    }
+}
+
 impl LimitableVotingMethodMarker for VotingFunction {}
 
 impl VotingMethodMarker for VotingFunction {}
@@ -103,6 +200,10 @@ impl VotingMethod for VotingFunction {
             }
         }
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        self.required_variables()
+    }
 }
 
 impl DisplayTree for VotingFunction {
@@ -111,12 +212,12 @@ impl DisplayTree for VotingFunction {
             VotingFunction::Single(value, was_root) => {
                 if *was_root {
                     write!(f, "{{")?;
-                    f.indent(2);
+                    f.indent(1);
                     write!(f, "\n")?;
                 }
                 DisplayTree::fmt(value, f)?;
                 if *was_root {
-                    f.dedent(2);
+                    f.dedent(1);
                     write!(f, "\n")?;
                     write!(f, "}}")?;
                 }
@@ -160,6 +261,43 @@ pub enum VotingOperation {
     }
 }
 
+impl VotingOperation {
+    fn simplify(self) -> Self {
+        match self {
+            VotingOperation::IterScope { expr } => {
+                VotingOperation::IterScope { expr: expr.simplify() }
+            }
+            VotingOperation::GlobalScope { expr } => {
+                VotingOperation::GlobalScope { expr: expr.simplify() }
+            }
+            VotingOperation::AggregationScope { variable_name, op, expr } => {
+                VotingOperation::AggregationScope { variable_name, op, expr: expr.simplify() }
+            }
+            other => other
+        }
+    }
+
+    fn collect_voter_variables(&self, target: &mut Vec<&'static str>) {
+        match self {
+            VotingOperation::IterScope { expr } | VotingOperation::AggregationScope { expr, .. } => {
+                expr.visit_variables(&mut |access| {
+                    if let VariableAccess::Read(name) = access {
+                        push_voter_variable(name, target)
+                    }
+                })
+            }
+            VotingOperation::GlobalScope { .. } => {}
+            VotingOperation::Execute { execution, .. } => {
+                for value in execution.required_voter_fields() {
+                    if !target.contains(&value) {
+                        target.push(value)
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl VotingMethod for VotingOperation {
     fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value>
         where
@@ -255,6 +393,20 @@ impl VotingMethod for VotingExecution {
             }
         }
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        match self {
+            VotingExecution::BuildIn(value) => {
+                value.required_voter_fields()
+            }
+            VotingExecution::Parsed(_, value) => {
+                value.required_variables()
+            }
+            VotingExecution::Limited(value) => {
+                value.required_voter_fields()
+            }
+        }
+    }
 }
 
 impl DisplayTree for VotingExecution {
@@ -295,6 +447,30 @@ impl VotingExecutableList {
             _ => Some(Self::Multiple(values))
         }
     }
+
+    fn simplify(self) -> Self {
+        match self {
+            VotingExecutableList::Single(value) => {
+                VotingExecutableList::Single(Box::new(value.simplify()))
+            }
+            VotingExecutableList::Multiple(values) => {
+                VotingExecutableList::Multiple(values.into_iter().map(VotingExpressionOrStatement::simplify).collect())
+            }
+        }
+    }
+
+    fn visit_variables(&self, visitor: &mut impl FnMut(VariableAccess)) {
+        match self {
+            VotingExecutableList::Single(value) => {
+                value.visit_variables(visitor)
+            }
+            VotingExecutableList::Multiple(values) => {
+                for value in values {
+                    value.visit_variables(visitor)
+                }
+            }
+        }
+    }
 }
 
 impl VotingExecutable for VotingExecutableList {
@@ -323,7 +499,7 @@ impl DisplayTree for VotingExecutableList {
             }
             VotingExecutableList::Multiple(value) => {
                 write!(f, "{{")?;
-                f.indent(2);
+                f.indent(1);
                 write!(f, "\n")?;
                 for (p, v) in value.iter().with_position() {
                     DisplayTree::fmt(v, f)?;
@@ -335,7 +511,7 @@ impl DisplayTree for VotingExecutableList {
                         }
                     }
                 }
-                f.dedent(2);
+                f.dedent(1);
                 write!(f, "\n")?;
                 write!(f, "}}")
             }
@@ -368,6 +544,20 @@ impl InnerIfElse {
     pub fn from_expr(((cond, if_block), else_block): ((VotingExpression, VotingExecutableList), VotingExecutableList)) -> Self {
         Self::new(cond, if_block, else_block)
     }
+
+    fn simplify(self) -> Self {
+        Self {
+            cond: Box::new(self.cond.simplify()),
+            if_block: self.if_block.simplify(),
+            else_block: self.else_block.simplify()
+        }
+    }
+
+    fn visit_variables(&self, visitor: &mut impl FnMut(VariableAccess)) {
+        self.cond.visit_variables(visitor);
+        self.if_block.visit_variables(visitor);
+        self.else_block.visit_variables(visitor);
+    }
 }
 
 impl VotingExecutable for InnerIfElse {
@@ -413,6 +603,28 @@ impl VotingExpressionOrStatement {
     pub fn pack_stmt(stmt: VotingStatement) -> Self {
         Self::Statement {stmt: stmt.into()}
     }
+
+    fn simplify(self) -> Self {
+        match self {
+            VotingExpressionOrStatement::Expression { expr } => {
+                VotingExpressionOrStatement::Expression { expr: expr.simplify() }
+            }
+            VotingExpressionOrStatement::Statement { stmt } => {
+                VotingExpressionOrStatement::Statement { stmt: Box::new(stmt.simplify()) }
+            }
+        }
+    }
+
+    fn visit_variables(&self, visitor: &mut impl FnMut(VariableAccess)) {
+        match self {
+            VotingExpressionOrStatement::Expression { expr } => {
+                expr.visit_variables(visitor)
+            }
+            VotingExpressionOrStatement::Statement { stmt } => {
+                stmt.visit_variables(visitor)
+            }
+        }
+    }
 }
 
 impl VotingExecutable for VotingExpressionOrStatement {
@@ -476,6 +688,32 @@ pub enum VotingStatement {
     }
 }
 
+impl VotingStatement {
+    fn simplify(self) -> Self {
+        match self {
+            VotingStatement::If { cond, if_block } => {
+                VotingStatement::If { cond: cond.simplify(), if_block: if_block.simplify() }
+            }
+            VotingStatement::SetVariable { variable_name, expression } => {
+                VotingStatement::SetVariable { variable_name, expression: expression.simplify() }
+            }
+        }
+    }
+
+    fn visit_variables(&self, visitor: &mut impl FnMut(VariableAccess)) {
+        match self {
+            VotingStatement::If { cond, if_block } => {
+                cond.visit_variables(visitor);
+                if_block.visit_variables(visitor);
+            }
+            VotingStatement::SetVariable { variable_name, expression } => {
+                expression.visit_variables(visitor);
+                visitor(VariableAccess::Write(variable_name));
+            }
+        }
+    }
+}
+
 impl VotingExecutable for VotingStatement {
     fn execute(&self, context: &mut impl ContextWithMutableVariables) -> VotingResult<Value>
     {
@@ -530,10 +768,41 @@ pub enum VotingExpression {
 }
 
 impl VotingExpression {
+    fn simplify(self) -> Self {
+        match self {
+            VotingExpression::Expr(value) => {
+                VotingExpression::Expr(fold_constants(&value).unwrap_or(value))
+            }
+            VotingExpression::IfElse(value) => {
+                VotingExpression::IfElse(value.simplify())
+            }
+            other => other
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn parse_as_single(s: ParserInput) -> EvalexprResult<Self> {
         Ok(VotingExpression::Expr(evalexpr::build_operator_tree(s.deref())?))
     }
+
+    fn visit_variables(&self, visitor: &mut impl FnMut(VariableAccess)) {
+        match self {
+            VotingExpression::Expr(value) => {
+                for name in value.iter_read_variable_identifiers() {
+                    visitor(VariableAccess::Read(name))
+                }
+                for name in value.iter_write_variable_identifiers() {
+                    visitor(VariableAccess::Write(name))
+                }
+            }
+            VotingExpression::IfElse(value) => {
+                value.visit_variables(visitor)
+            }
+            VotingExpression::TupleGet { variable_name, .. } => {
+                visitor(VariableAccess::Read(variable_name))
+            }
+        }
+    }
 }
 
 impl VotingExecutable for VotingExpression {
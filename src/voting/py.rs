@@ -12,7 +12,7 @@
 //See the License for the specific language governing permissions and
 //limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use evalexpr::{Context, ContextWithMutableVariables, EvalexprError, EvalexprResult, FloatType, IntType, Value};
 use itertools::Itertools;
 use pyo3::{Bound, FromPyObject, IntoPy, PyAny, pyclass, pymethods, PyObject, PyResult, Python};
@@ -185,9 +185,9 @@ impl PyContextWithMutableVariables {
         unsafe{&*self.inner}.get_value(item).is_some()
     }
 
-    pub fn get_all_values(&self) -> HashMap<String, PyExprValue> {
+    pub fn get_all_values(&self) -> BTreeMap<String, PyExprValue> {
         unsafe{&*self.inner}
-            .variable_map()
+            .variable_map_ordered()
             .into_iter()
             .map(|(k, v)| (k, v.into()))
             .collect()
@@ -14,23 +14,164 @@
 
 use std::borrow::{Borrow};
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::{Arc, RwLock};
-use crate::voting::{VotingFunction};
+use evalexpr::{Context, ContextWithMutableVariables, EvalexprResult, Value};
+use nom::Finish;
+use thiserror::Error;
+use crate::voting::{VotingFunction, VotingMethod, VotingMethodContext, VotingResult};
+use crate::voting::parser::{parse, InterpretedVoting};
+use crate::voting::parser::input::ParserInput;
+use crate::voting::traits::VotingMethodMarker;
+
+/// An object safe variant of a [VotingMethod], allows to register
+/// voting methods implemented outside of this crate in a [VotingRegistry].
+pub trait DynVotingMethod: Send + Sync + Debug {
+    fn execute_dyn(&self, global_context: &mut dyn VotingMethodContext, voters: &mut [&mut dyn VotingMethodContext]) -> VotingResult<Value>;
+
+    fn required_voter_fields_dyn(&self) -> Vec<&'static str>;
+}
+
+impl<T> DynVotingMethod for T where T: VotingMethodMarker + Debug {
+    fn execute_dyn(&self, global_context: &mut dyn VotingMethodContext, voters: &mut [&mut dyn VotingMethodContext]) -> VotingResult<Value> {
+        let mut global_context = DynContext(global_context);
+        let mut voters = voters.iter_mut().map(|value| DynContext(&mut **value)).collect::<Vec<_>>();
+        self.execute(&mut global_context, voters.as_mut_slice())
+    }
+
+    fn required_voter_fields_dyn(&self) -> Vec<&'static str> {
+        self.required_voter_fields()
+    }
+}
+
+/// Wraps a dynamic context to be usable as generic [VotingMethodContext].
+#[repr(transparent)]
+struct DynContext<'a>(&'a mut dyn VotingMethodContext);
+
+impl Context for DynContext<'_> {
+    delegate::delegate! {
+        to self.0 {
+            fn get_value(&self, identifier: &str) -> Option<&Value>;
+            fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value>;
+            fn are_builtin_functions_disabled(&self) -> bool;
+            fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()>;
+        }
+    }
+}
+
+impl ContextWithMutableVariables for DynContext<'_> {
+    delegate::delegate! {
+        to self.0 {
+            fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()>;
+        }
+    }
+}
+
+impl VotingMethodContext for DynContext<'_> {
+    fn variable_map(&self) -> HashMap<String, Value> {
+        self.0.variable_map()
+    }
+}
 
 /// A registry for votings
+///
+/// When parsing a name the build in votings take precedence over everything in the registry,
+/// a registered [VotingFunction] takes precedence over a registered voting method with the same name.
+///
+/// Parsed votings are immutable and can be cloned and shared between translations,
+/// [VotingRegistry::parse_once] caches them by their source.
 #[derive(Clone, Debug, Default)]
 pub struct VotingRegistry {
-    inner: Arc<RwLock<HashMap<String, Arc<VotingFunction>>>>
+    inner: Arc<RwLock<HashMap<String, Arc<VotingFunction>>>>,
+    methods: Arc<RwLock<HashMap<String, Arc<dyn DynVotingMethod>>>>,
+    parsed: Arc<RwLock<ParsedVotings>>
+}
+
+/// The maximum number of votings cached by [VotingRegistry::parse_once].
+const PARSED_VOTINGS_LIMIT: usize = 1024;
+
+/// The cache of [VotingRegistry::parse_once].
+///
+/// The generation changes with every registration, a voting parsed before
+/// a registration is not cached after it.
+#[derive(Debug, Default)]
+struct ParsedVotings {
+    generation: u64,
+    votings: HashMap<String, Arc<InterpretedVoting>>
+}
+
+/// The error when a voting can not be parsed by [VotingRegistry::parse_once].
+#[derive(Debug, Clone, Error)]
+#[error("Failed to parse the voting \"{voting}\": {message}")]
+pub struct VotingParseError {
+    pub voting: String,
+    pub message: String
 }
 
 impl VotingRegistry {
     pub fn new() -> Self {
-        Self { inner: Default::default() }
+        Self { inner: Default::default(), methods: Default::default(), parsed: Default::default() }
+    }
+
+    /// Parses `voting` with this registry, the same `voting` returns the same shared instance
+    /// until a voting is registered. At most 1024 votings are cached,
+    /// the cache is dropped when it is full.
+    pub fn parse_once(&self, voting: &str) -> Result<Arc<InterpretedVoting>, VotingParseError> {
+        let generation = {
+            let parsed = self.parsed.read().unwrap();
+            if let Some(parsed) = parsed.votings.get(voting) {
+                return Ok(parsed.clone())
+            }
+            parsed.generation
+        };
+        let parsed = match parse::<nom::error::Error<_>>(ParserInput::new(voting, self)).finish() {
+            Ok((_, parsed)) => Arc::new(parsed),
+            Err(err) => {
+                return Err(VotingParseError { voting: voting.to_string(), message: err.to_string() })
+            }
+        };
+        let mut cache = self.parsed.write().unwrap();
+        if cache.generation != generation {
+            // Something was registered while parsing, the result may already be outdated.
+            return Ok(parsed)
+        }
+        if cache.votings.len() >= PARSED_VOTINGS_LIMIT && !cache.votings.contains_key(voting) {
+            cache.votings.clear();
+        }
+        // A concurrent parse of the same voting may have finished first, keep the first instance.
+        Ok(cache.votings.entry(voting.to_string()).or_insert(parsed).clone())
+    }
+
+    /// Drops the parsed votings, registered names can change the result of a parse.
+    /// Has to be called after the registration is visible.
+    fn invalidate_parsed(&self) {
+        let mut cache = self.parsed.write().unwrap();
+        cache.generation = cache.generation.wrapping_add(1);
+        cache.votings.clear();
+    }
+
+    /// Registers a custom voting method for `name` and returns the replaced method.
+    pub fn register_method<M>(&self, name: String, method: M) -> Option<Arc<dyn DynVotingMethod>> where M: VotingMethodMarker + Debug + 'static {
+        let old = self.methods.write().unwrap().insert(name, Arc::new(method));
+        self.invalidate_parsed();
+        old
+    }
+
+    pub fn get_method<Q: ?Sized>(&self, q: &Q) -> Option<Arc<dyn DynVotingMethod>> where
+        String: Borrow<Q>,
+        Q: Hash + Eq
+    {
+        self.methods
+            .read()
+            .unwrap()
+            .get(q)
+            .cloned()
     }
 
     pub fn register_arc(&self, name: String, voting_function: Arc<VotingFunction>) -> (Arc<VotingFunction>, Option<Arc<VotingFunction>>) {
         let old = self.inner.write().unwrap().insert(name, voting_function.clone());
+        self.invalidate_parsed();
         (voting_function, old)
     }
 
@@ -51,3 +192,56 @@ impl VotingRegistry {
 }
 
 
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use evalexpr::Value;
+    use crate::voting::{VotingMethod, VotingMethodContext, VotingResult};
+    use crate::voting::registry::{VotingRegistry, PARSED_VOTINGS_LIMIT};
+    use crate::voting::traits::VotingMethodMarker;
+
+    #[derive(Debug)]
+    struct AlwaysFortyTwo;
+
+    impl VotingMethod for AlwaysFortyTwo {
+        fn execute<A, B>(&self, _: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
+            Ok(Value::Float(42.0 + voters.len() as f64))
+        }
+
+        fn required_voter_fields(&self) -> Vec<&'static str> {
+            Vec::new()
+        }
+    }
+
+    impl VotingMethodMarker for AlwaysFortyTwo {}
+
+    #[test]
+    fn parse_once_shares_the_parsed_voting(){
+        let registry = VotingRegistry::new();
+        let first = registry.parse_once("PCombSum").unwrap();
+        let second = registry.parse_once("PCombSum").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(first.is_build_in());
+
+        let other = registry.parse_once("CombSum").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        let clone = registry.clone();
+        assert!(Arc::ptr_eq(&first, &clone.parse_once("PCombSum").unwrap()));
+
+        assert!(registry.register_method("forty_two".to_string(), AlwaysFortyTwo).is_none());
+        assert!(!Arc::ptr_eq(&first, &registry.parse_once("PCombSum").unwrap()));
+        assert!(registry.parse_once("forty_two").unwrap().is_registered());
+    }
+
+    #[test]
+    fn parse_once_limits_the_cache(){
+        let registry = VotingRegistry::new();
+        let first = registry.parse_once("PCombSum").unwrap();
+        for i in 0..PARSED_VOTINGS_LIMIT {
+            registry.parse_once(&format!("aggregate(let sss = sumOf): score * {i}")).unwrap();
+        }
+        assert!(registry.parsed.read().unwrap().votings.len() <= PARSED_VOTINGS_LIMIT);
+        assert!(!Arc::ptr_eq(&first, &registry.parse_once("PCombSum").unwrap()));
+    }
+}
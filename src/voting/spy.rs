@@ -14,25 +14,149 @@
 
 use std::sync::Mutex;
 use evalexpr::{Value};
+use itertools::Itertools;
+use pyo3::{pyclass, pymethods};
 use crate::variable_names::{CANDIDATE_ID, SCORE, SCORE_CANDIDATE, TOPIC_ID, VOTER_ID};
-use crate::voting::{VotingMethod, VotingMethodContext, VotingMethodMarker, VotingResult};
+use crate::voting::{move_positions_to_front, VotingMethod, VotingMethodContext, VotingMethodMarker, VotingResult};
 use crate::voting::traits::RootVotingMethodMarker;
+use crate::voting::audit::AuditingContext;
+
+/// The default number of bins for [Spy::collect_score_distribution].
+pub const DEFAULT_HISTOGRAM_BINS: usize = 50;
 
 /// Allows to spy on the voting method
 pub struct Spy<V: VotingMethodMarker + ?Sized> {
     spy_history: Mutex<Vec<(usize, (usize, f64, Value), Vec<(usize, f64)>)>>,
+    histogram_bins: usize,
+    audit: bool,
+    last_audit_log: Mutex<Vec<String>>,
     inner: V,
 }
 
 impl<V> Spy<V> where V: VotingMethodMarker {
     pub fn new(inner: V) -> Self {
-        Self { inner, spy_history: Default::default() }
+        Self { inner, spy_history: Default::default(), histogram_bins: DEFAULT_HISTOGRAM_BINS, audit: false, last_audit_log: Default::default() }
+    }
+
+    /// If set, the names of all variables read by the last voting are recorded. (See [Spy::last_audit_log])
+    pub fn with_audit_log(mut self, audit: bool) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// The names of the variables read by the last voting, first from the global context
+    /// and then from the voters in their original order.
+    /// Empty if the audit log is not activated.
+    pub fn last_audit_log(&self) -> Vec<String> {
+        self.last_audit_log.lock().unwrap().clone()
+    }
+
+    /// Sets the number of bins used by [Spy::collect_score_distribution]. (at least 1)
+    pub fn with_histogram_bins(mut self, histogram_bins: usize) -> Self {
+        self.histogram_bins = histogram_bins.max(1);
+        self
     }
 
     #[allow(dead_code)]
     pub fn spy_history(&self) -> &Mutex<Vec<(usize, (usize, f64, Value), Vec<(usize, f64)>)>> {
         &self.spy_history
     }
+
+    /// Collects the distribution of the numeric results recorded so far.
+    /// Returns None if no numeric result was recorded.
+    pub fn collect_score_distribution(&self) -> Option<ScoreDistribution> {
+        let lock = self.spy_history.lock().unwrap();
+        let scores = lock.iter().filter_map(|(_, (_, _, result), _)| result.as_number().ok()).collect_vec();
+        drop(lock);
+        ScoreDistribution::new(scores, self.histogram_bins)
+    }
+}
+
+/// Statistics about the scores produced by a voting.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct ScoreDistribution {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    /// The lower bound of each bin and the number of scores in it.
+    pub histogram: Vec<(f64, usize)>
+}
+
+impl ScoreDistribution {
+    /// Creates the distribution for `scores`, NaN values are ignored.
+    pub fn new(mut scores: Vec<f64>, histogram_bins: usize) -> Option<Self> {
+        scores.retain(|value| !value.is_nan());
+        if scores.is_empty() {
+            return None
+        }
+        scores.sort_by(f64::total_cmp);
+        let min = scores[0];
+        let max = scores[scores.len() - 1];
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let std_dev = (scores.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / scores.len() as f64).sqrt();
+
+        let histogram_bins = histogram_bins.max(1);
+        let bin_width = (max - min) / histogram_bins as f64;
+        let mut histogram = (0..histogram_bins).map(|bin| (min + bin as f64 * bin_width, 0usize)).collect_vec();
+        for value in scores.iter() {
+            let bin = if bin_width > 0.0 {
+                (((value - min) / bin_width) as usize).min(histogram_bins - 1)
+            } else {
+                0
+            };
+            histogram[bin].1 += 1;
+        }
+
+        Some(
+            Self {
+                min,
+                max,
+                mean,
+                std_dev,
+                p25: percentile(&scores, 0.25),
+                p50: percentile(&scores, 0.5),
+                p75: percentile(&scores, 0.75),
+                histogram
+            }
+        )
+    }
+}
+
+/// Linear interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let pos = p * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+#[pymethods]
+impl ScoreDistribution {
+    #[getter]
+    fn get_min(&self) -> f64 {self.min}
+    #[getter]
+    fn get_max(&self) -> f64 {self.max}
+    #[getter]
+    fn get_mean(&self) -> f64 {self.mean}
+    #[getter]
+    fn get_std_dev(&self) -> f64 {self.std_dev}
+    #[getter]
+    fn get_p25(&self) -> f64 {self.p25}
+    #[getter]
+    fn get_p50(&self) -> f64 {self.p50}
+    #[getter]
+    fn get_p75(&self) -> f64 {self.p75}
+    #[getter]
+    fn get_histogram(&self) -> Vec<(f64, usize)> {self.histogram.clone()}
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
 }
 
 impl<V> VotingMethod for Spy<V> where V: VotingMethodMarker {
@@ -41,7 +165,20 @@ impl<V> VotingMethod for Spy<V> where V: VotingMethodMarker {
     }
 
     fn execute_with_voters<'a, A, B>(&self, global_context: &mut A, voters: &'a mut [B]) -> VotingResult<(Value, &'a [B])> where A: VotingMethodContext, B: VotingMethodContext {
-        let (result, voters) = self.inner.execute_with_voters(global_context, voters)?;
+        let (result, voters) = if self.audit {
+            let mut audited_global = AuditingContext::new(&mut *global_context);
+            let mut audited_voters = voters.iter_mut().map(AuditingContext::new).collect_vec();
+            let (result, used) = self.inner.execute_with_voters(&mut audited_global, &mut audited_voters)?;
+            let used = used.iter().map(|value| value.get_ref() as *const B).collect_vec();
+            let mut audit_log = audited_global.into_audit_log().1;
+            for voter in audited_voters {
+                audit_log.extend(voter.into_audit_log().1);
+            }
+            *self.last_audit_log.lock().unwrap() = audit_log;
+            (result, &*move_to_front(voters, &used))
+        } else {
+            self.inner.execute_with_voters(global_context, voters)?
+        };
 
         let entry = (
             global_context.get_value(TOPIC_ID).unwrap().as_int()? as usize,
@@ -64,10 +201,24 @@ impl<V> VotingMethod for Spy<V> where V: VotingMethodMarker {
 
         return Ok((result, voters))
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        self.inner.required_voter_fields()
+    }
 }
 
 impl<V> VotingMethodMarker for Spy<V> where V: VotingMethodMarker{}
 
+/// Moves the voters at the addresses in `used` to the front, keeping the order of `used`.
+/// Returns the moved voters.
+fn move_to_front<'a, B>(voters: &'a mut [B], used: &[*const B]) -> &'a mut [B] {
+    let order = used.iter().map(|ptr| {
+        voters.iter().position(|value| std::ptr::eq(value, *ptr)).expect("The used voters have to be part of the voters!")
+    }).collect_vec();
+    move_positions_to_front(voters, &order);
+    &mut voters[..used.len()]
+}
+
 /// Allows to limit the voting to the top n elements
 pub trait IntoSpy: RootVotingMethodMarker {
     #[allow(dead_code)]
@@ -79,3 +230,68 @@ impl<T> IntoSpy for T where T: Sized + RootVotingMethodMarker {
         Spy::new(self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroUsize;
+    use evalexpr::{Context, ContextWithMutableVariables, HashMapContext};
+    use crate::variable_names::{CANDIDATE_ID, RANK, SCORE, SCORE_CANDIDATE, TOPIC_ID, VOTER_ID};
+    use crate::voting::spy::{IntoSpy, Spy};
+    use crate::voting::traits::IntoVotingWithLimit;
+    use crate::voting::{BuildInVoting, VotingMethod};
+
+    #[test]
+    fn audit_log_records_the_variables_of_the_used_voters(){
+        let spy = Spy::new(BuildInVoting::CombSum.with_limit(NonZeroUsize::new(1).unwrap())).with_audit_log(true);
+        let mut global = HashMapContext::new();
+        global.set_value(TOPIC_ID.to_string(), 0.into()).unwrap();
+        global.set_value(CANDIDATE_ID.to_string(), 0.into()).unwrap();
+        global.set_value(SCORE_CANDIDATE.to_string(), 1.0.into()).unwrap();
+        let mut voters = (0..2).map(|voter_id| {
+            let mut voter = HashMapContext::new();
+            voter.set_value(VOTER_ID.to_string(), (voter_id as i64).into()).unwrap();
+            voter.set_value(SCORE.to_string(), (0.25 * (voter_id + 1) as f64).into()).unwrap();
+            voter.set_value(RANK.to_string(), (2 - voter_id as i64).into()).unwrap();
+            voter
+        }).collect::<Vec<_>>();
+
+        let (result, used) = spy.execute_with_voters(&mut global, &mut voters).unwrap();
+        assert_eq!(0.5, result.as_number().unwrap());
+        assert_eq!(1, used.len());
+        assert_eq!(1, used[0].get_value(VOTER_ID).unwrap().as_int().unwrap());
+
+        let audit_log = spy.last_audit_log();
+        assert_eq!(1, audit_log.iter().filter(|value| value.as_str() == SCORE).count());
+        assert!(audit_log.iter().all(|value| value.as_str() == SCORE || value.as_str() == RANK));
+    }
+
+    #[test]
+    fn uniform_scores_produce_a_symmetric_histogram(){
+        let spy = BuildInVoting::OriginalScore.spy().with_histogram_bins(10);
+        assert!(spy.collect_score_distribution().is_none());
+
+        for i in 0..100 {
+            let mut global = HashMapContext::new();
+            global.set_value(TOPIC_ID.to_string(), 0.into()).unwrap();
+            global.set_value(CANDIDATE_ID.to_string(), (i as i64).into()).unwrap();
+            global.set_value(SCORE_CANDIDATE.to_string(), (i as f64).into()).unwrap();
+            let mut voter = HashMapContext::new();
+            voter.set_value(VOTER_ID.to_string(), 0.into()).unwrap();
+            voter.set_value(SCORE.to_string(), 1.0.into()).unwrap();
+            spy.execute(&mut global, &mut [voter]).unwrap();
+        }
+
+        let distribution = spy.collect_score_distribution().unwrap();
+        assert_eq!(0.0, distribution.min);
+        assert_eq!(99.0, distribution.max);
+        assert_eq!(49.5, distribution.mean);
+        assert_eq!(49.5, distribution.p50);
+        assert_eq!(24.75, distribution.p25);
+        assert_eq!(74.25, distribution.p75);
+        assert_eq!(10, distribution.histogram.len());
+        let counts = distribution.histogram.iter().map(|(_, count)| *count).collect::<Vec<_>>();
+        assert_eq!(100, counts.iter().sum::<usize>());
+        let reversed = counts.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(counts, reversed);
+    }
+}
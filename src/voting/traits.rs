@@ -41,6 +41,10 @@ impl<T> VotingMethod for Box<T> where T: VotingMethodMarker {
     fn execute<A, B>(&self, global_context: &mut A, voters: &mut [B]) -> VotingResult<Value> where A: VotingMethodContext, B: VotingMethodContext {
         self.as_ref().execute(global_context, voters)
     }
+
+    fn required_voter_fields(&self) -> Vec<&'static str> {
+        self.as_ref().required_voter_fields()
+    }
 }
 
 impl<T> VotingMethodMarker for Box<T> where T: VotingMethodMarker {}
@@ -15,7 +15,7 @@
 #![allow(dead_code)]
 
 use std::fmt::{Display, Formatter};
-use evalexpr::{Node, Operator};
+use evalexpr::{build_operator_tree, EmptyContext, Node, Operator, Value};
 use itertools::Itertools;
 use strum::EnumIs;
 
@@ -56,19 +56,108 @@ pub(crate) enum NodeContainer<'a> {
     Leaf(&'a Node, bool),
     Single(&'a Node, Box<NodeContainer<'a>>, bool),
     Expr(Box<NodeContainer<'a>>, &'a Node, Box<NodeContainer<'a>>, bool),
-    Special(&'a Node, Vec<NodeContainer<'a>>, bool)
+    Special(&'a Node, Vec<NodeContainer<'a>>, bool),
+    /// A constant subexpression folded to a literal.
+    Folded(String)
 }
 
 impl<'a> NodeContainer<'a> {
 
-    fn origin(&self) -> &'a Node {
+    fn origin(&self) -> Option<&'a Node> {
         match self {
-            NodeContainer::Leaf(value, _) => {*value}
-            NodeContainer::Single(value, _, _) => {*value}
-            NodeContainer::Expr(_, value, _, _) => {*value}
-            NodeContainer::Special(value, _, _) => {*value}
+            NodeContainer::Leaf(value, _) => {Some(*value)}
+            NodeContainer::Single(value, _, _) => {Some(*value)}
+            NodeContainer::Expr(_, value, _, _) => {Some(*value)}
+            NodeContainer::Special(value, _, _) => {Some(*value)}
+            NodeContainer::Folded(_) => {None}
         }
     }
+
+    /// Replaces the largest constant subexpressions with literals.
+    fn fold_constants(self, folded_any: &mut bool) -> Self {
+        if let Some(node) = self.origin() {
+            if !is_literal(node) {
+                let literal = node
+                    .eval_with_context(&EmptyContext)
+                    .ok()
+                    .and_then(|value| to_literal(&value));
+                if let Some(literal) = literal {
+                    *folded_any = true;
+                    return NodeContainer::Folded(literal)
+                }
+            }
+        }
+        match self {
+            NodeContainer::Single(node, child, is_root) => {
+                NodeContainer::Single(node, child.fold_constants(folded_any).into(), is_root)
+            }
+            NodeContainer::Expr(left, node, right, is_root) => {
+                NodeContainer::Expr(
+                    left.fold_constants(folded_any).into(),
+                    node,
+                    right.fold_constants(folded_any).into(),
+                    is_root
+                )
+            }
+            NodeContainer::Special(node, children, is_root) => {
+                NodeContainer::Special(
+                    node,
+                    children.into_iter().map(|value| value.fold_constants(folded_any)).collect_vec(),
+                    is_root
+                )
+            }
+            other => other
+        }
+    }
+}
+
+/// Returns true if the node is a constant or a root containing only a constant.
+fn is_literal(node: &Node) -> bool {
+    match node.operator() {
+        Operator::Const { .. } => true,
+        Operator::RootNode => {
+            matches!(node.children(), [child] if matches!(child.operator(), Operator::Const { .. }))
+        }
+        _ => false
+    }
+}
+
+/// Converts a value to a literal, if the literal evaluates to exactly the same value.
+fn to_literal(value: &Value) -> Option<String> {
+    let literal = match value {
+        Value::Int(value) if *value < 0 => format!("({value})"),
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) if !value.is_finite() => return None,
+        Value::Float(value) if value.is_sign_negative() => format!("({value:?})"),
+        Value::Float(value) => format!("{value:?}"),
+        Value::Boolean(value) => value.to_string(),
+        _ => return None
+    };
+    let reparsed = build_operator_tree(&literal).ok()?.eval_with_context(&EmptyContext).ok()?;
+    let is_same = match (value, &reparsed) {
+        (Value::Int(a), Value::Int(b)) => a == b,
+        (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        _ => false
+    };
+    is_same.then_some(literal)
+}
+
+/// Folds the constant subexpressions of `node`.
+///
+/// Only subexpressions without variables and functions are folded. Returns [None] if nothing
+/// was folded or if the node can not be rendered and reparsed faithfully.
+pub(crate) fn fold_constants(node: &Node) -> Option<Node> {
+    let container = walk_left_to_right(node);
+    if build_operator_tree(&container.to_string()).ok()? != *node {
+        return None
+    }
+    let mut folded_any = false;
+    let folded = container.fold_constants(&mut folded_any);
+    if !folded_any {
+        return None
+    }
+    build_operator_tree(&folded.to_string()).ok()
 }
 
 impl Display for NodeContainer<'_> {
@@ -110,6 +199,9 @@ impl Display for NodeContainer<'_> {
                 }
 
             }
+            NodeContainer::Folded(value) => {
+                write!(f, "{value}")
+            }
         }
     }
 }
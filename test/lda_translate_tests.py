@@ -0,0 +1,41 @@
+# Copyright 2024 Felix Engl
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+import unittest
+
+import numpy
+
+from ldatranslate import PyTopicModel, PyVocabulary
+
+
+class NumpyRoundTripTest(unittest.TestCase):
+    def test_matrix_survives_round_trip(self):
+        matrix = numpy.array([[0.5, 0.25, 0.25], [0.1, 0.2, 0.7]], dtype=numpy.float64)
+        model = PyTopicModel.from_numpy(matrix, PyVocabulary("en", ["a", "b", "c"]), [1, 2, 3])
+        self.assertEqual(2, model.k)
+        numpy.testing.assert_array_equal(matrix, model.to_numpy())
+
+    def test_non_contiguous_matrix_is_copied(self):
+        matrix = numpy.asfortranarray(numpy.array([[0.5, 0.25, 0.25], [0.1, 0.2, 0.7]], dtype=numpy.float64))
+        self.assertFalse(matrix.flags["C_CONTIGUOUS"])
+        model = PyTopicModel.from_numpy(matrix, PyVocabulary("en", ["a", "b", "c"]), [1, 2, 3])
+        numpy.testing.assert_array_equal(matrix, model.to_numpy())
+
+    def test_rejects_wrong_shape(self):
+        with self.assertRaises(ValueError):
+            PyTopicModel.from_numpy(numpy.zeros((2, 2)), PyVocabulary("en", ["a", "b", "c"]), [1, 2, 3])
+
+
+if __name__ == '__main__':
+    unittest.main()